@@ -0,0 +1,252 @@
+// Copyright 2025, Collabora, Ltd.
+//
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Mapping variant spellings of copyright holders to a canonical holder.
+
+use std::fmt::Display;
+
+use crate::Holder;
+
+/// Replaces the name and/or e-mail of holders matching a pattern.
+///
+/// A holder matches if its name matches `match_name` (ignoring case and extra whitespace)
+/// and its e-mail matches `match_email` (ignoring case), skipping whichever is `None`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HolderAlias {
+    /// The canonical name, or `None` to keep the name as written
+    pub name: Option<String>,
+    /// The canonical e-mail, or `None` to keep the e-mail as written
+    pub email: Option<String>,
+    pub match_name: Option<String>,
+    pub match_email: Option<String>,
+}
+
+fn name_key(name: &str) -> String {
+    Holder::new(name).name_key()
+}
+
+impl HolderAlias {
+    fn matches(&self, holder: &Holder) -> bool {
+        let name_matches = self
+            .match_name
+            .as_deref()
+            .is_none_or(|name| name_key(name) == holder.name_key());
+        let email_matches = self.match_email.as_deref().is_none_or(|email| {
+            holder
+                .email
+                .as_deref()
+                .is_some_and(|e| e.eq_ignore_ascii_case(email))
+        });
+        (self.match_name.is_some() || self.match_email.is_some()) && name_matches && email_matches
+    }
+
+    fn apply(&self, holder: &Holder) -> Holder {
+        Holder {
+            name: self.name.clone().unwrap_or_else(|| holder.name.clone()),
+            email: self.email.clone().or_else(|| holder.email.clone()),
+            url: holder.url.clone(),
+        }
+    }
+}
+
+/// A holder that was replaced by its canonical form.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AliasResolution {
+    pub original: Holder,
+    pub resolved: Holder,
+}
+
+impl Display for AliasResolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "\"{}\" -> \"{}\"", self.original, self.resolved)
+    }
+}
+
+/// A line of a mailmap file that could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Could not parse line {line_number} of mailmap: {line}")]
+pub struct MailmapError {
+    pub line_number: usize,
+    pub line: String,
+}
+
+/// Split a mailmap line into (name, e-mail) pairs: "Name <email> Other <other>"
+fn mailmap_pairs(line: &str) -> Option<Vec<(Option<String>, String)>> {
+    let mut pairs = vec![];
+    let mut rest = line;
+    while let Some(open) = rest.find('<') {
+        let close = open + rest[open..].find('>')?;
+        let name = rest[..open].trim();
+        pairs.push((
+            (!name.is_empty()).then(|| name.to_string()),
+            rest[open + 1..close].trim().to_string(),
+        ));
+        rest = &rest[close + 1..];
+    }
+    rest.trim().is_empty().then_some(pairs)
+}
+
+/// Strip a comment: anything from a '#' that is not inside an e-mail address
+fn strip_comment(line: &str) -> &str {
+    let mut in_email = false;
+    for (pos, c) in line.char_indices() {
+        match c {
+            '<' => in_email = true,
+            '>' => in_email = false,
+            '#' if !in_email => return &line[..pos],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn non_empty(text: String) -> Option<String> {
+    (!text.is_empty()).then_some(text)
+}
+
+/// A list of aliases, applied in order: the first one that matches a holder is used.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HolderAliases {
+    aliases: Vec<HolderAlias>,
+}
+
+impl HolderAliases {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, alias: HolderAlias) {
+        self.aliases.push(alias);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.aliases.is_empty()
+    }
+
+    /// Parse a file in git `.mailmap` syntax. Each line is one of:
+    ///
+    /// - `Proper Name <commit@email>`
+    /// - `<proper@email> <commit@email>`
+    /// - `Proper Name <proper@email> <commit@email>`
+    /// - `Proper Name <proper@email> Commit Name <commit@email>`
+    ///
+    /// As an extension, either e-mail may be empty (`<>`): an empty proper e-mail keeps the e-mail as written,
+    /// and an empty commit e-mail matches on the name alone, for holders written without an e-mail.
+    pub fn parse_mailmap(text: &str) -> Result<Self, MailmapError> {
+        let mut aliases = Self::new();
+        for (index, raw_line) in text.lines().enumerate() {
+            let line = strip_comment(raw_line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            let error = || MailmapError {
+                line_number: index + 1,
+                line: raw_line.to_string(),
+            };
+            let alias = match mailmap_pairs(line).ok_or_else(error)?.as_slice() {
+                [(Some(name), match_email)] => HolderAlias {
+                    name: Some(name.clone()),
+                    email: None,
+                    match_name: None,
+                    match_email: non_empty(match_email.clone()),
+                },
+                [(name, email), (match_name, match_email)] => HolderAlias {
+                    name: name.clone(),
+                    email: non_empty(email.clone()),
+                    match_name: match_name.clone(),
+                    match_email: non_empty(match_email.clone()),
+                },
+                _ => return Err(error()),
+            };
+            if alias.match_name.is_none() && alias.match_email.is_none() {
+                return Err(error());
+            }
+            aliases.add(alias);
+        }
+        Ok(aliases)
+    }
+
+    /// The canonical form of this holder, if an alias matches and changes it.
+    pub fn resolve(&self, holder: &Holder) -> Option<Holder> {
+        self.aliases
+            .iter()
+            .find(|alias| alias.matches(holder))
+            .map(|alias| alias.apply(holder))
+            .filter(|resolved| resolved != holder)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Copyright, Holder, HolderAlias, HolderAliases, YearRangeNormalization};
+
+    #[test]
+    fn mailmap() {
+        let aliases = HolderAliases::parse_mailmap(
+            "# Collabora
+Collabora, Ltd. <> Collabora Ltd <>
+Collabora, Ltd. <info@collabora.com> <info@collabora.co.uk> # old domain
+Jane Doe <jane@example.com>
+<jane@example.com> <jdoe@example.org>
+",
+        )
+        .unwrap();
+        let resolve = |holder: &str| {
+            aliases
+                .resolve(&Holder::parse(holder))
+                .map(|h| h.to_string())
+        };
+        assert_eq!(resolve("Collabora LTD").as_deref(), Some("Collabora, Ltd."));
+        assert_eq!(
+            resolve("Collabora Ltd. <info@collabora.co.uk>").as_deref(),
+            Some("Collabora, Ltd. <info@collabora.com>")
+        );
+        assert_eq!(
+            resolve("J. Doe <jane@example.com>").as_deref(),
+            Some("Jane Doe <jane@example.com>")
+        );
+        assert_eq!(
+            resolve("Jane Doe <jdoe@example.org>").as_deref(),
+            Some("Jane Doe <jane@example.com>")
+        );
+        assert_eq!(resolve("Collabora, Ltd."), None);
+        assert_eq!(resolve("Bob <jane@example.net>"), None);
+
+        let error = HolderAliases::parse_mailmap("Jane Doe\n<a@b> <c@d>\nJane <jane@example.com")
+            .unwrap_err();
+        assert_eq!(error.line_number, 1);
+        assert_eq!(
+            HolderAliases::parse_mailmap("<a@b> <c@d>\nJane <jane@example.com")
+                .unwrap_err()
+                .line_number,
+            2
+        );
+    }
+
+    #[test]
+    fn resolve_copyright() {
+        let mut aliases = HolderAliases::new();
+        aliases.add(HolderAlias {
+            name: Some("Collabora, Ltd.".to_string()),
+            email: None,
+            match_name: Some("Collabora Ltd".to_string()),
+            match_email: None,
+        });
+        let (copyright, resolutions) = Copyright::try_parse_with_aliases(
+            YearRangeNormalization::default(),
+            &aliases,
+            "Copyright 2019, Collabora Ltd\nCopyright 2020, Jane Doe",
+        )
+        .unwrap();
+        assert_eq!(
+            copyright.to_string(),
+            "2019, Collabora, Ltd.\n2020, Jane Doe"
+        );
+        assert_eq!(resolutions.len(), 1);
+        assert_eq!(
+            resolutions[0].to_string(),
+            "\"Collabora Ltd\" -> \"Collabora, Ltd.\""
+        );
+    }
+}
@@ -0,0 +1,263 @@
+// Copyright 2025, Collabora, Ltd.
+//
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Finding variant spellings of the same copyright holder, to suggest aliases.
+
+use std::{collections::HashMap, fmt::Display};
+
+use itertools::Itertools;
+
+use crate::{holder::CORPORATE_SUFFIXES, Holder};
+
+/// Split a name into lower-case words, treating punctuation as whitespace.
+fn folded_words(name: &str) -> Vec<String> {
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Fold a holder name for comparison: ignore case and punctuation,
+/// a leading "the", and any trailing corporate suffixes.
+///
+/// So "The Foo Co., Ltd." and "foo" fold to the same thing.
+pub fn fold_holder_name(name: &str) -> String {
+    let suffixes = CORPORATE_SUFFIXES
+        .iter()
+        .map(|suffix| folded_words(suffix))
+        .collect_vec();
+    let mut words = folded_words(name);
+    if words.len() > 1 && words[0] == "the" {
+        words.remove(0);
+    }
+    while let Some(suffix) = suffixes
+        .iter()
+        .find(|suffix| words.len() > suffix.len() && words.ends_with(suffix))
+    {
+        words.truncate(words.len() - suffix.len());
+    }
+    words.join(" ")
+}
+
+/// The Levenshtein distance between two strings, in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Options controlling which holders `cluster_holders` considers the same.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HolderClusterOptions {
+    /// The most single-character edits between folded names for them to be considered the same.
+    ///
+    /// Short names need proportionally fewer: at least five characters per edit,
+    /// so "Bob" and "Rob" stay apart.
+    pub max_edit_distance: usize,
+}
+
+impl Default for HolderClusterOptions {
+    fn default() -> Self {
+        Self {
+            max_edit_distance: 2,
+        }
+    }
+}
+
+/// Holders that appear to be the same, with the number of files naming each.
+///
+/// Displays as lines in `.mailmap` syntax (see `HolderAliases::parse_mailmap`) mapping each variant
+/// to the canonical holder, with the file counts in comments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HolderCluster {
+    /// The holder named by the most files, followed by the other variants, most common first
+    pub holders: Vec<(Holder, usize)>,
+}
+
+impl HolderCluster {
+    /// The suggested canonical form: the most common variant
+    pub fn canonical(&self) -> &Holder {
+        &self.holders[0].0
+    }
+
+    /// The number of files naming any of the variants
+    pub fn file_count(&self) -> usize {
+        self.holders.iter().map(|(_, count)| count).sum()
+    }
+}
+
+impl Display for HolderCluster {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let canonical = self.canonical();
+        writeln!(
+            f,
+            "# {canonical}: {} files, {} variants",
+            self.file_count(),
+            self.holders.len()
+        )?;
+        let proper_email = canonical.email.as_deref().unwrap_or_default();
+        for (holder, count) in &self.holders[1..] {
+            writeln!(
+                f,
+                "{} <{proper_email}> {} <{}> # {count} files",
+                canonical.name,
+                holder.name,
+                holder.email.as_deref().unwrap_or_default()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn is_similar(options: HolderClusterOptions, a: (&Holder, &str), b: (&Holder, &str)) -> bool {
+    let ((a, a_folded), (b, b_folded)) = (a, b);
+    if let (Some(a_email), Some(b_email)) = (&a.email, &b.email) {
+        if a_email.eq_ignore_ascii_case(b_email) {
+            return true;
+        }
+    }
+    if a_folded.is_empty() || b_folded.is_empty() {
+        return false;
+    }
+    let shorter = a_folded.chars().count().min(b_folded.chars().count());
+    let longer = a_folded.chars().count().max(b_folded.chars().count());
+    if longer - shorter > options.max_edit_distance {
+        return false;
+    }
+    let distance = edit_distance(a_folded, b_folded);
+    distance <= options.max_edit_distance && distance * 5 <= shorter
+}
+
+/// Find the root of `index`, flattening the path as we go.
+fn find_root(parents: &mut [usize], mut index: usize) -> usize {
+    while parents[index] != index {
+        parents[index] = parents[parents[index]];
+        index = parents[index];
+    }
+    index
+}
+
+/// Group holders that appear to be variants of each other, given the number of files naming each.
+///
+/// Holders are grouped if their names are the same after folding (see `fold_holder_name`),
+/// they share an e-mail address, or their folded names are within the edit distance in `options`.
+/// Grouping is transitive. Only groups with more than one variant are returned, largest first.
+pub fn cluster_holders(
+    holders: impl IntoIterator<Item = (Holder, usize)>,
+    options: HolderClusterOptions,
+) -> Vec<HolderCluster> {
+    let mut counts: HashMap<Holder, usize> = HashMap::new();
+    for (holder, count) in holders {
+        *counts.entry(holder).or_default() += count;
+    }
+    let holders = counts.into_iter().sorted().collect_vec();
+    let folded = holders
+        .iter()
+        .map(|(holder, _)| fold_holder_name(&holder.name))
+        .collect_vec();
+
+    let mut parents = (0..holders.len()).collect_vec();
+    for (a, b) in (0..holders.len()).tuple_combinations() {
+        if is_similar(
+            options,
+            (&holders[a].0, &folded[a]),
+            (&holders[b].0, &folded[b]),
+        ) {
+            let (a_root, b_root) = (find_root(&mut parents, a), find_root(&mut parents, b));
+            parents[a_root] = b_root;
+        }
+    }
+
+    let mut groups: HashMap<usize, Vec<(Holder, usize)>> = HashMap::new();
+    for (index, holder) in holders.into_iter().enumerate() {
+        groups
+            .entry(find_root(&mut parents, index))
+            .or_default()
+            .push(holder);
+    }
+    groups
+        .into_values()
+        .filter(|group| group.len() > 1)
+        .map(|group| HolderCluster {
+            // Most common first, preferring forms with an e-mail address
+            holders: group
+                .into_iter()
+                .sorted_by(|(a, a_count), (b, b_count)| {
+                    b_count
+                        .cmp(a_count)
+                        .then_with(|| b.email.is_some().cmp(&a.email.is_some()))
+                        .then_with(|| a.cmp(b))
+                })
+                .collect(),
+        })
+        .sorted_by(|a, b| {
+            b.file_count()
+                .cmp(&a.file_count())
+                .then_with(|| a.canonical().cmp(b.canonical()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use itertools::Itertools;
+
+    use crate::{cluster_holders, fold_holder_name, Holder, HolderAliases, HolderClusterOptions};
+
+    #[test]
+    fn folding() {
+        assert_eq!(fold_holder_name("Collabora, Ltd."), "collabora");
+        assert_eq!(fold_holder_name("The Foo Co., Ltd."), "foo");
+        assert_eq!(fold_holder_name("Foo Bar GmbH"), "foo bar");
+        assert_eq!(fold_holder_name("The Chromium Authors"), "chromium authors");
+        // Not left empty
+        assert_eq!(fold_holder_name("Ltd"), "ltd");
+    }
+
+    #[test]
+    fn clusters() {
+        let holders = [
+            ("Collabora, Ltd.", 12),
+            ("Collabora Ltd", 3),
+            ("collabora ltd.", 1),
+            ("Colabora Ltd.", 1),
+            ("Jane Doe <jane@example.com>", 5),
+            ("J. Doe <jane@example.com>", 1),
+            ("Bob", 2),
+            ("Rob", 2),
+            ("Example Corp.", 4),
+        ]
+        .map(|(holder, count)| (Holder::parse(holder), count));
+        let clusters = cluster_holders(holders.clone(), HolderClusterOptions::default());
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].canonical().to_string(), "Collabora, Ltd.");
+        assert_eq!(clusters[0].file_count(), 17);
+        assert_eq!(
+            clusters[1].to_string(),
+            "# Jane Doe <jane@example.com>: 6 files, 2 variants\n\
+             Jane Doe <jane@example.com> J. Doe <jane@example.com> # 1 files\n"
+        );
+
+        // The report can be used as an alias file
+        let aliases =
+            HolderAliases::parse_mailmap(&clusters.iter().map(|c| c.to_string()).join("")).unwrap();
+        for (holder, _) in &holders {
+            let resolved = aliases.resolve(holder).unwrap_or_else(|| holder.clone());
+            let expected = clusters
+                .iter()
+                .find(|cluster| cluster.holders.iter().any(|(h, _)| h == holder))
+                .map_or(holder, |cluster| cluster.canonical());
+            assert_eq!(&resolved, expected);
+        }
+    }
+}
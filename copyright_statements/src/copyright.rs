@@ -2,22 +2,38 @@
 //
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
 use itertools::Itertools;
 use nom::Finish;
 
-use crate::{copyright_parsing, raw_year::traits::YearRangeNormalizationOptions, years::YearSpec};
+use crate::{
+    aliases::{AliasResolution, HolderAliases},
+    copyright_parsing,
+    diagnostics::{self, CopyrightDecompositionError, CopyrightErrorReason},
+    diff::{self, CopyrightDiff},
+    holder::{split_holder_names, Holder, HolderIdentities},
+    raw_year::{options::YearRangeNormalization, traits::YearRangeNormalizationOptions},
+    years::{CoalescingPolicy, OpenRangeStyle, TracedYearSpec, YearRangeCollection, YearSpec},
+};
 
+/// A single copyright line, split into years and holder.
+///
+/// The years may be empty, for statements like "Copyright The Chromium Authors".
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DecomposedCopyright {
     pub years: Vec<YearSpec>,
-    pub holder: String,
+    pub holder: Holder,
 }
 
 impl DecomposedCopyright {
-    fn contains(&self, other: &DecomposedCopyright) -> bool {
-        self.holder.trim() == other.holder.trim()
+    /// Does this line cover everything that the other line does?
+    ///
+    /// The holder must be the same, as `identities` decides. A line with no years is contained by any line for the same holder,
+    /// but a line with no years only contains other lines with no years.
+    fn contains(&self, other: &DecomposedCopyright, identities: &HolderIdentities) -> bool {
+        identities.resolve(&self.holder) == identities.resolve(&other.holder)
             && other.years.iter().all(|other_spec| {
                 // all of the other copyright's ranges must be included in some of our specs
                 self.years
@@ -28,9 +44,19 @@ impl DecomposedCopyright {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 pub enum Copyright {
     Decomposable(DecomposedCopyright),
     MultilineDecomposable(Vec<DecomposedCopyright>),
+    /// Some lines could be decomposed, while others are just free text ("All rights reserved.")
+    PartiallyDecomposable {
+        decomposed: Vec<DecomposedCopyright>,
+        residue: Vec<String>,
+    },
     Complex(String),
 }
 
@@ -38,19 +64,57 @@ impl DecomposedCopyright {
     pub fn new(years: &[YearSpec], holder: &str) -> Self {
         Self {
             years: years.into(),
-            holder: holder.trim().to_string(),
+            holder: Holder::parse(holder),
         }
     }
     pub fn new_from_single_yearspec(yearspec: &YearSpec, holder: &str) -> Self {
         Self {
             years: vec![yearspec.clone()],
-            holder: holder.trim().to_string(),
+            holder: Holder::parse(holder),
         }
     }
+
+    /// Coalesce the years into the simplest sorted list: "2021, 2019-2020" becomes "2019-2021".
+    pub fn normalize(self) -> Self {
+        let mut years = YearRangeCollection::new();
+        years.extend(self.years);
+        Self {
+            years: years.into_coalesced_specs(CoalescingPolicy::Strict),
+            holder: self.holder,
+        }
+    }
+
+    /// Split a line naming several holders ("2020, Alice and Bob") into one line per holder,
+    /// each with the same years.
+    pub fn split_holders(&self) -> Vec<DecomposedCopyright> {
+        split_holder_names(&self.holder.to_string())
+            .into_iter()
+            .map(|holder| DecomposedCopyright::new(&self.years, &holder))
+            .collect()
+    }
+
+    /// Format, choosing how to output open-ended year ranges.
+    pub fn to_string_with(&self, style: OpenRangeStyle) -> String {
+        if self.years.is_empty() {
+            return self.holder.to_string();
+        }
+        format!(
+            "{}, {}",
+            self.years
+                .iter()
+                .map(|y| y.to_string_with(style))
+                .join(", "),
+            self.holder
+        )
+    }
 }
 
-fn vec_contains_decomposed(m: &[DecomposedCopyright], d2: &DecomposedCopyright) -> bool {
-    m.iter().any(|d| d.contains(d2))
+fn vec_contains_decomposed(
+    m: &[DecomposedCopyright],
+    d2: &DecomposedCopyright,
+    identities: &HolderIdentities,
+) -> bool {
+    m.iter().any(|d| d.contains(d2, identities))
 }
 
 impl Copyright {
@@ -60,32 +124,298 @@ impl Copyright {
     ) -> Result<Self, CopyrightDecompositionError> {
         let copyright = copyright_parsing::copyright_lines(options)(statement)
             .finish()
-            .map(|(_leftover, parsed)| parsed)?;
+            .map(|(_leftover, parsed)| parsed)
+            .map_err(|e| CopyrightDecompositionError::from_nom(statement, e))?;
         Ok(copyright)
     }
 
-    pub fn contains(&self, other: &Copyright) -> bool {
+    /// Like `try_parse`, also returning every year of the decomposed lines with how it was worked out,
+    /// so that years that relied on a heuristic (see `YearProvenance::is_heuristic`) can be reviewed.
+    pub fn try_parse_with_provenance(
+        options: impl YearRangeNormalizationOptions + Copy,
+        statement: &str,
+    ) -> Result<(Self, Vec<TracedYearSpec>), CopyrightDecompositionError> {
+        copyright_parsing::traced_copyright_lines(options)(statement)
+            .finish()
+            .map(|(_leftover, parsed)| parsed)
+            .map_err(|e| CopyrightDecompositionError::from_nom(statement, e))
+    }
+
+    /// Like `try_parse`, then replace holders with their canonical form (see `resolve_aliases`).
+    pub fn try_parse_with_aliases(
+        options: impl YearRangeNormalizationOptions + Copy,
+        aliases: &HolderAliases,
+        statement: &str,
+    ) -> Result<(Self, Vec<AliasResolution>), CopyrightDecompositionError> {
+        Ok(Self::try_parse(options, statement)?.resolve_aliases(aliases))
+    }
+
+    /// Replace each holder matching an alias with its canonical form,
+    /// returning the replacements made.
+    pub fn resolve_aliases(self, aliases: &HolderAliases) -> (Self, Vec<AliasResolution>) {
+        let mut resolutions = vec![];
+        let residue = self.residue_lines().into_iter().map(String::from).collect();
+        let decomposed = self
+            .decomposed_lines()
+            .iter()
+            .cloned()
+            .map(|mut line| {
+                if let Some(resolved) = aliases.resolve(&line.holder) {
+                    resolutions.push(AliasResolution {
+                        original: std::mem::replace(&mut line.holder, resolved.clone()),
+                        resolved,
+                    });
+                }
+                line
+            })
+            .collect();
+        if resolutions.is_empty() {
+            return (self, resolutions);
+        }
+        (Copyright::from_parts(decomposed, residue), resolutions)
+    }
+
+    /// Like `try_parse`, but fail if any line that looks like a copyright statement
+    /// could not be decomposed, rather than keeping it as free text.
+    pub fn try_parse_strict(
+        options: impl YearRangeNormalizationOptions + Copy,
+        statement: &str,
+    ) -> Result<Self, CopyrightDecompositionError> {
+        if let Some(error) = Self::diagnose(options, statement).into_iter().next() {
+            return Err(error);
+        }
+        Self::try_parse(options, statement)
+    }
+
+    /// Explain why each line that looks like a copyright statement could not be decomposed.
+    ///
+    /// Lines of free text such as "All rights reserved." are not reported.
+    pub fn diagnose(
+        options: impl YearRangeNormalizationOptions + Copy,
+        statement: &str,
+    ) -> Vec<CopyrightDecompositionError> {
+        diagnostics::diagnose(options, statement)
+    }
+
+    /// Assemble from decomposed lines and free-text lines, choosing the simplest variant.
+    pub fn from_parts(mut decomposed: Vec<DecomposedCopyright>, residue: Vec<String>) -> Self {
+        if decomposed.is_empty() {
+            Copyright::Complex(residue.join("\n"))
+        } else if !residue.is_empty() {
+            Copyright::PartiallyDecomposable {
+                decomposed,
+                residue,
+            }
+        } else if decomposed.len() == 1 {
+            Copyright::Decomposable(decomposed.pop().expect("know there is one"))
+        } else {
+            Copyright::MultilineDecomposable(decomposed)
+        }
+    }
+
+    /// Get the lines that were decomposed into years and holder
+    pub fn decomposed_lines(&self) -> &[DecomposedCopyright] {
+        match self {
+            Copyright::Decomposable(d) => std::slice::from_ref(d),
+            Copyright::MultilineDecomposable(m) => m,
+            Copyright::PartiallyDecomposable { decomposed, .. } => decomposed,
+            Copyright::Complex(_) => &[],
+        }
+    }
+
+    /// Get the non-empty lines of free text that could not be decomposed
+    pub fn residue_lines(&self) -> Vec<&str> {
         match self {
-            Copyright::Decomposable(d) => match other {
-                Copyright::Decomposable(d2) => d.contains(d2),
-                Copyright::MultilineDecomposable(m2) => m2.iter().all(|d2| d.contains(d2)),
-                Copyright::Complex(_) => false,
-            },
-            Copyright::MultilineDecomposable(m) => match other {
-                Copyright::Decomposable(d2) => vec_contains_decomposed(m, d2),
-                Copyright::MultilineDecomposable(m2) => {
-                    m2.iter().all(|d2| vec_contains_decomposed(m, d2))
+            Copyright::Decomposable(_) | Copyright::MultilineDecomposable(_) => vec![],
+            Copyright::PartiallyDecomposable { residue, .. } => {
+                residue.iter().map(String::as_str).collect()
+            }
+            Copyright::Complex(c) => c
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Put into a canonical form, so that equivalent statements compare and hash equal.
+    ///
+    /// Lines for the same holder (see `Holder::is_same_holder`) are merged, as are lines for a name
+    /// alone and for the only form of that name with contact details, keeping the most complete
+    /// form of the holder, and their years coalesced. Holders are sorted by name, while lines of free
+    /// text are kept as they are, in their original order.
+    pub fn normalize(self) -> Self {
+        self.normalize_with(CoalescingPolicy::Strict)
+    }
+
+    /// Like `normalize`, coalescing the years of each holder as far as the policy allows.
+    pub fn normalize_with(self, coalescing: CoalescingPolicy) -> Self {
+        let residue: Vec<String> = self.residue_lines().into_iter().map(String::from).collect();
+
+        let decomposed = self
+            .years_per_holder()
+            .into_iter()
+            .map(|(holder, years)| DecomposedCopyright {
+                years: years.into_coalesced_specs(coalescing),
+                holder,
+            })
+            .sorted_by_cached_key(|line| (line.holder.name_key(), line.holder.clone()))
+            .collect();
+        Copyright::from_parts(decomposed, residue)
+    }
+
+    /// The holders of the decomposed lines of these statements, to decide which are the same.
+    pub(crate) fn holder_identities<'a>(
+        statements: impl IntoIterator<Item = &'a Copyright>,
+    ) -> HolderIdentities {
+        HolderIdentities::new(
+            statements
+                .into_iter()
+                .flat_map(|copyright| copyright.decomposed_lines())
+                .map(|line| &line.holder),
+        )
+    }
+
+    /// Group the years of the decomposed lines by holder (see `HolderIdentities`),
+    /// in order of first appearance.
+    pub(crate) fn years_per_holder(&self) -> Vec<(Holder, YearRangeCollection)> {
+        self.years_per_holder_with(&Copyright::holder_identities([self]))
+    }
+
+    /// Group the years of the decomposed lines by holder, as `identities` decides,
+    /// keeping the most complete form of each holder, in order of first appearance.
+    ///
+    /// Of forms that are equally complete, the least is kept, so the result does not depend on line order.
+    pub(crate) fn years_per_holder_with(
+        &self,
+        identities: &HolderIdentities,
+    ) -> Vec<(Holder, YearRangeCollection)> {
+        let mut holders: Vec<(Holder, YearRangeCollection)> = vec![];
+        for line in self.decomposed_lines() {
+            let key = identities.resolve(&line.holder);
+            match holders
+                .iter_mut()
+                .find(|(holder, _)| identities.resolve(holder) == key)
+            {
+                Some((holder, years)) => {
+                    if (!line.holder.has_contact(), &line.holder)
+                        < (!holder.has_contact(), &*holder)
+                    {
+                        *holder = line.holder.clone();
+                    }
+                    years.extend(line.years.iter().cloned());
                 }
-                Copyright::Complex(_) => false,
-            },
-            Copyright::Complex(c) => match other {
-                Copyright::Decomposable(_) => false,
-                Copyright::MultilineDecomposable(_) => false,
-                Copyright::Complex(c2) => c == c2,
-            },
+                None => {
+                    let mut years = YearRangeCollection::new();
+                    years.extend(line.years.iter().cloned());
+                    holders.push((line.holder.clone(), years));
+                }
+            }
+        }
+        holders
+    }
+
+    /// Everything in either statement: lines for the same holder are merged and their years coalesced.
+    ///
+    /// The result is normalized (see `normalize`).
+    pub fn union(&self, other: &Copyright) -> Copyright {
+        let decomposed = self
+            .decomposed_lines()
+            .iter()
+            .chain(other.decomposed_lines())
+            .cloned()
+            .collect();
+        let residue = self
+            .residue_lines()
+            .into_iter()
+            .chain(other.residue_lines())
+            .map(String::from)
+            .collect();
+        Copyright::from_parts(decomposed, residue).normalize()
+    }
+
+    /// What this statement covers that the other does not: holders missing from the other,
+    /// years missing from the other's lines for the same holder, and lines of free text
+    /// missing from the other.
+    ///
+    /// Returns `None` if the other contains everything in this one.
+    pub fn difference(&self, other: &Copyright) -> Option<Copyright> {
+        let identities = Copyright::holder_identities([self, other]);
+        let theirs = other.years_per_holder_with(&identities);
+        let decomposed: Vec<DecomposedCopyright> = self
+            .years_per_holder_with(&identities)
+            .into_iter()
+            .filter_map(|(holder, years)| {
+                let key = identities.resolve(&holder);
+                let Some((_, their_years)) = theirs
+                    .iter()
+                    .find(|(their_holder, _)| identities.resolve(their_holder) == key)
+                else {
+                    return Some(DecomposedCopyright {
+                        years: years.into_coalesced_specs(CoalescingPolicy::Strict),
+                        holder,
+                    });
+                };
+                let remaining = years.subtract(their_years.clone());
+                (!remaining.is_empty()).then(|| DecomposedCopyright {
+                    years: remaining.into_iter().map(YearSpec::from).collect(),
+                    holder,
+                })
+            })
+            .collect();
+        let their_residue = other.residue_lines();
+        let residue: Vec<String> = self
+            .residue_lines()
+            .into_iter()
+            .filter(|line| !their_residue.contains(line))
+            .map(String::from)
+            .unique()
+            .collect();
+        if decomposed.is_empty() && residue.is_empty() {
+            None
+        } else {
+            Some(Copyright::from_parts(decomposed, residue))
         }
     }
 
+    /// Describe how the other (newer) statement differs from this one, holder by holder.
+    pub fn diff(&self, new: &Copyright) -> CopyrightDiff {
+        diff::diff(self, new)
+    }
+
+    /// Split every decomposed line naming several holders into one line per holder.
+    ///
+    /// This is an optional step after parsing: holders are split on "and", "&", and commas,
+    /// keeping corporate suffixes like "Collabora, Ltd." and names like "Doe, Jane" intact.
+    /// See `split_holder_names`.
+    pub fn split_holders(self) -> Self {
+        let residue = self.residue_lines().into_iter().map(String::from).collect();
+        let decomposed = self
+            .decomposed_lines()
+            .iter()
+            .flat_map(DecomposedCopyright::split_holders)
+            .collect();
+        Copyright::from_parts(decomposed, residue)
+    }
+
+    /// Does this cover everything the other one does?
+    ///
+    /// Each decomposed line of the other must be contained in one of our decomposed lines,
+    /// and each line of free text in the other must appear exactly in ours.
+    pub fn contains(&self, other: &Copyright) -> bool {
+        let identities = Copyright::holder_identities([self, other]);
+        let decomposed = self.decomposed_lines();
+        let residue = self.residue_lines();
+        other
+            .decomposed_lines()
+            .iter()
+            .all(|d2| vec_contains_decomposed(decomposed, d2, &identities))
+            && other
+                .residue_lines()
+                .iter()
+                .all(|line| residue.contains(line))
+    }
+
     #[cfg(test)]
     fn is_complex(&self) -> bool {
         matches!(self, Copyright::Complex(_))
@@ -99,44 +429,70 @@ impl Copyright {
 
 impl Display for DecomposedCopyright {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}, {}",
-            self.years.iter().map(YearSpec::to_string).join(", "),
-            self.holder
-        )
+        write!(f, "{}", self.to_string_with(OpenRangeStyle::AsWritten))
     }
 }
 
-impl Display for Copyright {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl Copyright {
+    /// Format, choosing how to output open-ended year ranges.
+    pub fn to_string_with(&self, style: OpenRangeStyle) -> String {
         match self {
-            Copyright::Decomposable(c) => c.fmt(f),
+            Copyright::Decomposable(c) => c.to_string_with(style),
             Copyright::MultilineDecomposable(v) => {
-                write!(
-                    f,
-                    "{}",
-                    v.iter().map(DecomposedCopyright::to_string).join("\n")
-                )
+                v.iter().map(|c| c.to_string_with(style)).join("\n")
             }
-            Copyright::Complex(s) => write!(f, "{s}"),
+            Copyright::PartiallyDecomposable {
+                decomposed,
+                residue,
+            } => decomposed
+                .iter()
+                .map(|c| c.to_string_with(style))
+                .chain(residue.iter().cloned())
+                .join("\n"),
+            Copyright::Complex(s) => s.clone(),
         }
     }
 }
 
-#[derive(Debug, Clone, thiserror::Error)]
-#[error("Failed decomposing copyright: {0}")]
-pub struct CopyrightDecompositionError(String);
+impl FromStr for Copyright {
+    type Err = CopyrightDecompositionError;
+
+    /// Parse with the default options: see `try_parse`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Copyright::try_parse(YearRangeNormalization::default(), s)
+    }
+}
+
+impl FromStr for DecomposedCopyright {
+    type Err = CopyrightDecompositionError;
+
+    /// Parse a single line with the default options.
+    ///
+    /// Since this is known to be a copyright line, a line without years need not start with "Copyright",
+    /// so this parses what `Display` writes.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let options = YearRangeNormalization::default();
+        let line = s.trim();
+        copyright_parsing::parse_line(options, line)
+            .or_else(|| copyright_parsing::parse_line(options, &format!("Copyright {line}")))
+            .ok_or_else(|| {
+                CopyrightDecompositionError::new(s, CopyrightErrorReason::Unrecognized, 0..s.len())
+            })
+    }
+}
 
-impl From<nom::error::Error<&str>> for CopyrightDecompositionError {
-    fn from(value: nom::error::Error<&str>) -> Self {
-        CopyrightDecompositionError(value.to_string())
+impl Display for Copyright {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_string_with(OpenRangeStyle::AsWritten))
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::{Copyright, YearRangeNormalization};
+    use crate::{
+        raw_year::traits::SetYearRangeNormalizationOptions, CoalescingPolicy, Copyright,
+        OpenRangeStyle, YearRangeNormalization,
+    };
 
     #[test]
     fn contains() {
@@ -201,4 +557,250 @@ mod test {
         .unwrap();
         assert!(!two_liner.contains(&collabora_2021_thru_2023));
     }
+
+    #[test]
+    fn partial() {
+        let partial = Copyright::try_parse(
+            YearRangeNormalization::default(),
+            "Copyright 2020, Jane Doe
+            All rights reserved.
+            Copyright 2019-2021, Collabora, Ltd.",
+        )
+        .unwrap();
+        assert!(matches!(partial, Copyright::PartiallyDecomposable { .. }));
+        assert_eq!(partial.decomposed_lines().len(), 2);
+        assert_eq!(partial.residue_lines(), vec!["All rights reserved."]);
+        assert_eq!(
+            partial.to_string(),
+            "2020, Jane Doe\n2019-2021, Collabora, Ltd.\nAll rights reserved."
+        );
+
+        let jane =
+            Copyright::try_parse(YearRangeNormalization::default(), "2020, Jane Doe").unwrap();
+        assert!(partial.contains(&jane));
+        assert!(!jane.contains(&partial));
+
+        let residue_only =
+            Copyright::try_parse(YearRangeNormalization::default(), "All rights reserved.")
+                .unwrap();
+        assert!(residue_only.is_complex());
+        assert!(partial.contains(&residue_only));
+        assert!(partial.contains(&partial));
+
+        let other_residue = Copyright::try_parse(
+            YearRangeNormalization::default(),
+            "Copyright 2020, Jane Doe
+            Based on work by John Smith",
+        )
+        .unwrap();
+        assert!(!partial.contains(&other_residue));
+    }
+
+    #[test]
+    fn open_ranges() {
+        let options = YearRangeNormalization::default().reference_year(2025);
+        let open = Copyright::try_parse(options, "Copyright 2018-present, Jane Doe").unwrap();
+        assert_eq!(open.to_string(), "2018-present, Jane Doe");
+        assert_eq!(
+            open.to_string_with(OpenRangeStyle::Resolved),
+            "2018-2025, Jane Doe"
+        );
+        assert_eq!(
+            Copyright::try_parse(options, "Copyright Jane Doe since 2018")
+                .unwrap()
+                .to_string(),
+            "since 2018, Jane Doe"
+        );
+
+        let closed = Copyright::try_parse(options, "Copyright 2019-2020, Jane Doe").unwrap();
+        assert!(open.contains(&closed));
+        assert!(!closed.contains(&open));
+
+        // An open range in the future just covers its first year
+        let future = Copyright::try_parse(options, "Copyright 2030-, Jane Doe").unwrap();
+        assert_eq!(
+            future.to_string_with(OpenRangeStyle::Resolved),
+            "2030, Jane Doe"
+        );
+    }
+
+    #[test]
+    fn year_less() {
+        let chromium = Copyright::try_parse(
+            YearRangeNormalization::default(),
+            "Copyright The Chromium Authors",
+        )
+        .unwrap();
+        assert!(!chromium.is_complex());
+        assert_eq!(chromium.to_string(), "The Chromium Authors");
+
+        let jane = Copyright::try_parse(
+            YearRangeNormalization::default(),
+            "Copyright Jane Doe <jane@example.org>",
+        )
+        .unwrap();
+        assert!(!jane.is_complex());
+        assert_eq!(jane.to_string(), "Jane Doe <jane@example.org>");
+
+        // Years we could not parse are not part of the holder
+        assert!(Copyright::try_parse(
+            YearRangeNormalization::default(),
+            "Copyright 2020 1999 Jane Doe"
+        )
+        .unwrap()
+        .is_complex());
+
+        // Without a prefix, we cannot tell this is a copyright statement
+        assert!(
+            Copyright::try_parse(YearRangeNormalization::default(), "The Chromium Authors")
+                .unwrap()
+                .is_complex()
+        );
+
+        let chromium_2020 = Copyright::try_parse(
+            YearRangeNormalization::default(),
+            "Copyright 2020, The Chromium Authors",
+        )
+        .unwrap();
+        assert!(chromium_2020.contains(&chromium));
+        assert!(!chromium.contains(&chromium_2020));
+        assert!(chromium.contains(&chromium));
+
+        let mixed = Copyright::try_parse(
+            YearRangeNormalization::default(),
+            "Copyright The Chromium Authors
+            Copyright 2020, Jane Doe",
+        )
+        .unwrap();
+        assert!(mixed.is_multiline_decomposable());
+        assert_eq!(mixed.to_string(), "The Chromium Authors\n2020, Jane Doe");
+    }
+
+    #[test]
+    fn normalize() {
+        let copyright = Copyright::try_parse(
+            YearRangeNormalization::default(),
+            "Copyright 2021-2022, Foo
+            Copyright 2019, Foo <foo@example.org>
+            All rights reserved.
+            Copyright 2015, 2010, Bar",
+        )
+        .unwrap()
+        .normalize();
+        assert_eq!(
+            copyright.to_string(),
+            "2010, 2015, Bar\n2019, 2021-2022, Foo <foo@example.org>\nAll rights reserved."
+        );
+
+        // Equivalent statements normalize to the same value
+        let reordered = Copyright::try_parse(
+            YearRangeNormalization::default(),
+            "Copyright 2010, 2015, Bar
+            All rights reserved.
+            Copyright 2022, 2019, 2021, Foo <foo@example.org>",
+        )
+        .unwrap()
+        .normalize();
+        assert_eq!(copyright, reordered);
+
+        let line = Copyright::try_parse(YearRangeNormalization::default(), "2021, 2019-2020, Foo")
+            .unwrap()
+            .normalize();
+        assert_eq!(line.to_string(), "2019-2021, Foo");
+        assert!(matches!(line, Copyright::Decomposable(_)));
+
+        let gap =
+            Copyright::try_parse(YearRangeNormalization::default(), "2019, 2021, Foo").unwrap();
+        assert_eq!(gap.clone().normalize().to_string(), "2019, 2021, Foo");
+        assert_eq!(
+            gap.normalize_with(CoalescingPolicy::MaxGap(1)).to_string(),
+            "2019-2021, Foo"
+        );
+
+        // A name alone cannot be merged if there are several forms with contact details,
+        // whatever the order of the lines
+        let normalized = |statement: &str| {
+            Copyright::try_parse(YearRangeNormalization::default(), statement)
+                .unwrap()
+                .normalize()
+        };
+        let ambiguous = normalized(
+            "Copyright 2019, Jane Doe
+            Copyright 2020, Jane Doe <jane@example.com>
+            Copyright 2021, Jane Doe <jane@example.org>",
+        );
+        assert_eq!(
+            ambiguous.to_string(),
+            "2019, Jane Doe\n2020, Jane Doe <jane@example.com>\n2021, Jane Doe <jane@example.org>"
+        );
+        assert_eq!(
+            ambiguous,
+            normalized(
+                "Copyright 2021, Jane Doe <jane@example.org>
+                Copyright 2020, Jane Doe <jane@example.com>
+                Copyright 2019, Jane Doe"
+            )
+        );
+        assert_eq!(
+            normalized("Copyright 2019, jane doe\nCopyright 2020, Jane Doe"),
+            normalized("Copyright 2020, Jane Doe\nCopyright 2019, jane doe")
+        );
+
+        // Lines of free text keep their order, duplicates and all
+        assert_eq!(
+            normalized(
+                "Copyright 2020, Foo
+                Some rights reserved.
+                All rights reserved.
+                Some rights reserved."
+            )
+            .residue_lines(),
+            vec![
+                "Some rights reserved.",
+                "All rights reserved.",
+                "Some rights reserved."
+            ]
+        );
+    }
+
+    #[test]
+    fn split_holders() {
+        let copyright = Copyright::try_parse(
+            YearRangeNormalization::default(),
+            "Copyright 2020, Jane Doe and Collabora, Ltd.
+            All rights reserved.",
+        )
+        .unwrap()
+        .split_holders();
+        assert_eq!(
+            copyright.to_string(),
+            "2020, Jane Doe\n2020, Collabora, Ltd.\nAll rights reserved."
+        );
+
+        let single = Copyright::try_parse(
+            YearRangeNormalization::default(),
+            "Copyright 2019-2021, Collabora, Ltd.",
+        )
+        .unwrap()
+        .split_holders();
+        assert!(matches!(single, Copyright::Decomposable(_)));
+        assert_eq!(single.to_string(), "2019-2021, Collabora, Ltd.");
+
+        // A name repeating itself is one holder
+        let same_name = Copyright::try_parse(
+            YearRangeNormalization::default(),
+            "Copyright 2020, Johnson and Johnson",
+        )
+        .unwrap()
+        .split_holders();
+        assert_eq!(same_name.to_string(), "2020, Johnson and Johnson");
+
+        let surname_first = Copyright::try_parse(
+            YearRangeNormalization::default(),
+            "Copyright 2020, Doe, Jane",
+        )
+        .unwrap()
+        .split_holders();
+        assert_eq!(surname_first.to_string(), "2020, Doe, Jane");
+    }
 }
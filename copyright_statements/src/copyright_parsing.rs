@@ -5,125 +5,413 @@
 use nom::{
     branch::alt,
     bytes::complete::{tag, tag_no_case},
-    character::complete::{multispace0, not_line_ending, space0, space1},
-    combinator::{eof, map, map_opt, opt, recognize, rest, value, verify},
+    character::complete::{multispace0, not_line_ending, satisfy, space0, space1},
+    combinator::{all_consuming, consumed, map, map_opt, not, opt, recognize, value, verify},
+    error::{Error, ErrorKind},
     multi::{many1, separated_list1},
     sequence::{delimited, preceded, separated_pair, terminated, tuple},
-    IResult,
+    Err, IResult,
 };
 
 use crate::{
     copyright::{Copyright, DecomposedCopyright},
     raw_year::{
         self,
-        traits::{ConfigurableRawYearRange, YearRangeNormalizationOptions},
-        RawYear,
+        traits::{ConfigurableRawYear, ConfigurableRawYearRange, YearRangeNormalizationOptions},
+        FourDigitYear, RawYear, YearExpr,
     },
-    years::{Year, YearRange, YearSpec},
+    years::{OpenYearRange, TracedYearSpec, Year, YearProvenance, YearRange, YearSpec},
 };
 
+/// A decomposed line, along with how each of its years was worked out
+pub(crate) type TracedLine = (DecomposedCopyright, Vec<TracedYearSpec>);
+
+fn traced_line(years: Vec<TracedYearSpec>, holder: &str) -> TracedLine {
+    let specs: Vec<YearSpec> = years.iter().map(|year| year.spec.clone()).collect();
+    (DecomposedCopyright::new(&specs, holder), years)
+}
+
+/// How the century of a pair of raw years is known, if they can be turned into a year spec
+fn provenance((b, e): (YearExpr, YearExpr)) -> YearProvenance {
+    match (b, e) {
+        (YearExpr::FourDigit(_), YearExpr::FourDigit(_)) => YearProvenance::FourDigits,
+        (YearExpr::TwoDigit(b), YearExpr::TwoDigit(e)) if e < b => YearProvenance::Y2kSpanAssumed,
+        (YearExpr::TwoDigit(_), YearExpr::TwoDigit(_)) => YearProvenance::CenturyGuessed,
+        _ => YearProvenance::CenturyFromNeighbour,
+    }
+}
+
 fn year_spec(
     options: impl YearRangeNormalizationOptions + Copy,
 ) -> impl FnMut(&str) -> IResult<&str, YearSpec> {
+    move |input: &str| map(traced_year_spec(options), |year| year.spec)(input)
+}
+
+fn traced_year_spec(
+    options: impl YearRangeNormalizationOptions + Copy,
+) -> impl FnMut(&str) -> IResult<&str, TracedYearSpec> {
     // preceded and space0 are to remove leading spaces
     move |input: &str| {
         preceded(
             space0,
-            map_opt(raw_year::parse::year_spec, |(b, e)| {
-                if b == e {
-                    // single year
-                    Some(YearSpec::SingleYear(Year(b.to_four_digit().into_inner())))
-                } else {
-                    (b, e).try_to_four_digit_range(options).map(|(b, e)| {
-                        YearSpec::ClosedRange(YearRange::new(
-                            Year(b.into_inner()),
-                            Year(e.into_inner()),
-                        ))
+            alt((
+                map_opt(raw_year::parse::open_year_range, |(b, form)| {
+                    let begin = b
+                        .try_to_four_digit(options)
+                        .filter(|b| is_within_bounds(options, *b))?;
+                    Some(TracedYearSpec {
+                        spec: YearSpec::OpenRange(OpenYearRange::new(
+                            Year(begin.into_inner()),
+                            Year(options.get_reference_year()),
+                            form,
+                        )),
+                        provenance: provenance((b, b)),
                     })
-                }
-            }),
+                }),
+                closed_year_spec(options),
+            )),
         )(input)
     }
 }
 
+/// Is this year within the range of four-digit years the options let us recognize?
+fn is_within_bounds(options: impl YearRangeNormalizationOptions, year: FourDigitYear) -> bool {
+    let (earliest, latest) = options.get_four_digit_year_bounds();
+    (earliest..=latest).contains(&year.into_inner())
+}
+
+/// Turn a parsed pair of raw years into a single year or closed range
+///
+/// Returns `None` if the options do not let us work out the century, if a year is outside the
+/// recognized bounds, or if the range ends before it begins.
+pub(crate) fn raw_range_to_year_spec(
+    options: impl YearRangeNormalizationOptions + Copy,
+    (b, e): (YearExpr, YearExpr),
+) -> Option<YearSpec> {
+    if b == e {
+        // single year
+        b.try_to_four_digit(options)
+            .filter(|b| is_within_bounds(options, *b))
+            .map(|b| YearSpec::SingleYear(Year(b.into_inner())))
+    } else {
+        (b, e)
+            .try_to_four_digit_range(options)
+            .filter(|(b, e)| {
+                b <= e && is_within_bounds(options, *b) && is_within_bounds(options, *e)
+            })
+            .map(|(b, e)| {
+                YearSpec::ClosedRange(YearRange::new(Year(b.into_inner()), Year(e.into_inner())))
+            })
+    }
+}
+
+/// Like `raw_range_to_year_spec`, also recording how the years were worked out
+fn raw_range_to_traced_year_spec(
+    options: impl YearRangeNormalizationOptions + Copy,
+    pair: (YearExpr, YearExpr),
+) -> Option<TracedYearSpec> {
+    raw_range_to_year_spec(options, pair).map(|spec| TracedYearSpec {
+        spec,
+        provenance: provenance(pair),
+    })
+}
+
+/// A single year or closed range
+fn closed_year_spec(
+    options: impl YearRangeNormalizationOptions + Copy,
+) -> impl FnMut(&str) -> IResult<&str, TracedYearSpec> {
+    move |input: &str| {
+        alt((
+            verify(
+                map_opt(raw_year::parse::slash_year_pair, |pair| {
+                    raw_range_to_traced_year_spec(options, pair)
+                }),
+                |_| options.get_allow_slash_ranges(),
+            ),
+            map_opt(
+                consumed(raw_year::parse::year_spec),
+                |(text, (b, e)): (&str, _)| {
+                    let bare_two_digit_year = b == e
+                        && matches!(b, YearExpr::TwoDigit(_))
+                        && !text.trim_start().starts_with(raw_year::parse::APOSTROPHES);
+                    if bare_two_digit_year && options.get_require_apostrophe_for_two_digit_years() {
+                        return None;
+                    }
+                    raw_range_to_traced_year_spec(options, (b, e))
+                },
+            ),
+        ))(input)
+    }
+}
+
 fn year_spec_vec(
     options: impl YearRangeNormalizationOptions + Copy,
 ) -> impl FnMut(&str) -> IResult<&str, Vec<YearSpec>> {
+    move |input: &str| {
+        map(traced_year_spec_vec(options), |years| {
+            years.into_iter().map(|year| year.spec).collect()
+        })(input)
+    }
+}
+
+fn traced_year_spec_vec(
+    options: impl YearRangeNormalizationOptions + Copy,
+) -> impl FnMut(&str) -> IResult<&str, Vec<TracedYearSpec>> {
     move |input: &str| {
         separated_list1(
-            alt((delimited(space0, tag(","), space0), space1)),
-            year_spec(options),
+            alt((
+                delimited(space0, tag(","), space0),
+                // If not a range, a slash separates years in a list
+                tag("/"),
+                space1,
+            )),
+            traced_year_spec(options),
         )(input)
     }
 }
 
+/// Match a word (case-insensitive) as long as it is not just the beginning of a longer word
+fn whole_word<'a>(word: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, &'a str> {
+    terminated(tag_no_case(word), not(satisfy(char::is_alphanumeric)))
+}
+
+/// A single word or symbol that may introduce a copyright statement, such as
+/// "Copyright:", "Copr.", "(C)", "©", or "SPDX-FileCopyrightText:".
+/// "Portions" is accepted if followed by another one of these.
+fn copyright_prefix_word(input: &str) -> IResult<&str, ()> {
+    value(
+        (),
+        alt((
+            tag_no_case("SPDX-FileCopyrightText:"),
+            recognize(tuple((
+                whole_word("copyright"),
+                opt(tuple((space0, tag(":")))),
+            ))),
+            recognize(tuple((whole_word("copr"), opt(tag("."))))),
+            tag("©"),
+            tag_no_case("(c)"),
+            recognize(tuple((
+                whole_word("portions"),
+                space1,
+                copyright_prefix_word,
+            ))),
+        )),
+    )(input)
+}
+
+/// One or more copyright prefix words and symbols, possibly repeated: "Copyright: Copyright (c)"
+fn copyright_prefix_words(input: &str) -> IResult<&str, ()> {
+    value(
+        (),
+        tuple((
+            many1(preceded(multispace0, copyright_prefix_word)),
+            multispace0,
+        )),
+    )(input)
+}
+
+/// Optional copyright prefix words and symbols
 fn copyright_prefix() -> impl FnMut(&str) -> IResult<&str, ()> {
+    move |input: &str| value((), opt(copyright_prefix_words))(input)
+}
+
+/// Separates the years from the holder: a comma, "by", or both
+fn years_holder_separator(input: &str) -> IResult<&str, &str> {
+    alt((
+        recognize(tuple((
+            space0,
+            tag(","),
+            space0,
+            opt(tuple((whole_word("by"), space0))),
+        ))),
+        recognize(tuple((space1, whole_word("by"), space0))),
+    ))(input)
+}
+
+/// Years at the end of a line, possibly in parentheses and/or followed by a period:
+/// "2019-2021", "2004." or "(2004)"
+fn trailing_years(
+    options: impl YearRangeNormalizationOptions + Copy,
+) -> impl FnMut(&str) -> IResult<&str, Vec<TracedYearSpec>> {
     move |input: &str| {
-        value(
-            (),
-            opt(tuple((
-                multispace0,
-                alt((
-                    tag_no_case("copyright"),
-                    tag_no_case("copyright (C)"),
-                    tag_no_case("copr"),
-                )),
-                multispace0,
-            ))),
-        )(input)
+        all_consuming(terminated(
+            alt((
+                delimited(
+                    tag("("),
+                    traced_year_spec_vec(options),
+                    tuple((space0, tag(")"))),
+                ),
+                traced_year_spec_vec(options),
+            )),
+            tuple((opt(tag(".")), space0)),
+        ))(input)
     }
 }
 
+/// A line with the holder first and the years last: "Jane Doe 2019-2021" or "The Foo Project, 2004"
+fn holder_first_line(
+    options: impl YearRangeNormalizationOptions + Copy,
+) -> impl FnMut(&str) -> IResult<&str, TracedLine> {
+    move |input: &str| {
+        let (remaining, line) = not_line_ending(input)?;
+        // Try each place the trailing years might start, from the left, to get as many years as we can.
+        let candidates = line
+            .char_indices()
+            .zip(line.chars().skip(1))
+            .filter(|((_, c), next)| {
+                (c.is_whitespace() || *c == ',') && !next.is_whitespace() && *next != ','
+            })
+            .map(|((pos, c), _)| pos + c.len_utf8());
+        for pos in candidates {
+            let holder = line[..pos].trim_end().trim_end_matches(',').trim_end();
+            if !holder.chars().any(char::is_alphabetic) {
+                continue;
+            }
+            if let Ok((_, years)) = trailing_years(options)(&line[pos..]) {
+                return Ok((remaining, traced_line(years, holder)));
+            }
+        }
+        Err(Err::Error(Error::new(input, ErrorKind::Verify)))
+    }
+}
+
+/// A line with no years at all: "Copyright The Chromium Authors".
+///
+/// To tell this apart from any other text, it must start with a copyright prefix.
+/// It must also not start with a digit, which is more likely a year we could not parse.
+fn year_less_line(input: &str) -> IResult<&str, TracedLine> {
+    map(
+        preceded(
+            copyright_prefix_words,
+            verify(not_line_ending, |holder: &str| {
+                holder.chars().any(char::is_alphabetic)
+                    && !holder.starts_with(|c: char| c.is_ascii_digit())
+            }),
+        ),
+        |holder| traced_line(vec![], holder),
+    )(input)
+}
+
 fn copyright_line(
     options: impl YearRangeNormalizationOptions + Copy,
 ) -> impl FnMut(&str) -> IResult<&str, DecomposedCopyright> {
+    move |input: &str| map(traced_copyright_line(options), |(line, _)| line)(input)
+}
+
+fn traced_copyright_line(
+    options: impl YearRangeNormalizationOptions + Copy,
+) -> impl FnMut(&str) -> IResult<&str, TracedLine> {
     move |input: &str| {
-        map(
-            preceded(
-                // Might say "copyright" first
-                copyright_prefix(),
-                separated_pair(
-                    // Grab our years
-                    year_spec_vec(options),
-                    // could be separated by a comma with some optional spaces
-                    verify(recognize(tuple((space0, tag(","), space0))), |s: &str| {
-                        !s.is_empty()
-                    }),
-                    // Grab the rest of the line as the holder
-                    not_line_ending,
+        alt((
+            dated_copyright_line(options),
+            // Or, there might be no years
+            year_less_line,
+        ))(input)
+    }
+}
+
+fn dated_copyright_line(
+    options: impl YearRangeNormalizationOptions + Copy,
+) -> impl FnMut(&str) -> IResult<&str, TracedLine> {
+    move |input: &str| {
+        preceded(
+            // Might say "copyright" first
+            copyright_prefix(),
+            alt((
+                map(
+                    separated_pair(
+                        // Grab our years
+                        traced_year_spec_vec(options),
+                        // could be separated by a comma and/or "by", with some optional spaces
+                        years_holder_separator,
+                        // Grab the rest of the line as the holder
+                        not_line_ending,
+                    ),
+                    // Transform the tuple into a DecomposedCopyright
+                    |(year_spec, holder)| traced_line(year_spec, holder),
                 ),
-            ),
-            // Transform the tuple into a DecomposedCopyright
-            |(year_spec, holder)| DecomposedCopyright::new(&year_spec, holder),
+                // Or, the years might come last
+                holder_first_line(options),
+            )),
         )(input)
     }
 }
 
-/// For now, just distinguish a single decomposable line from anything else.
+/// Decompose a single (trimmed) line, if we can
+pub(crate) fn parse_line(
+    options: impl YearRangeNormalizationOptions + Copy,
+    line: &str,
+) -> Option<DecomposedCopyright> {
+    all_consuming(copyright_line(options))(line)
+        .ok()
+        .map(|(_, parsed)| parsed)
+}
+
+/// Decompose a single (trimmed) line, if we can, along with how each year was worked out
+pub(crate) fn parse_traced_line(
+    options: impl YearRangeNormalizationOptions + Copy,
+    line: &str,
+) -> Option<TracedLine> {
+    all_consuming(traced_copyright_line(options))(line)
+        .ok()
+        .map(|(_, parsed)| parsed)
+}
+
+/// Parse a whole string as a single year spec, if we can
+pub(crate) fn parse_year_spec(
+    options: impl YearRangeNormalizationOptions + Copy,
+    text: &str,
+) -> Option<YearSpec> {
+    all_consuming(terminated(year_spec(options), space0))(text)
+        .ok()
+        .map(|(_, parsed)| parsed)
+}
+
+/// Parse a whole string as a list of year specs, if we can
+pub(crate) fn parse_year_spec_list(
+    options: impl YearRangeNormalizationOptions + Copy,
+    text: &str,
+) -> Option<Vec<YearSpec>> {
+    all_consuming(terminated(year_spec_vec(options), space0))(text)
+        .ok()
+        .map(|(_, parsed)| parsed)
+}
+
+/// Does this line start with a copyright prefix, and thus is meant to be a copyright statement?
+pub(crate) fn has_copyright_prefix(line: &str) -> bool {
+    copyright_prefix_words(line).is_ok()
+}
+
+/// Decompose each line that we can, keeping the rest as free text.
 /// This will consume all remaining input
 pub(crate) fn copyright_lines(
     options: impl YearRangeNormalizationOptions + Copy,
 ) -> impl FnMut(&str) -> IResult<&str, Copyright> {
+    move |input: &str| map(traced_copyright_lines(options), |(copyright, _)| copyright)(input)
+}
+
+/// Like `copyright_lines`, also returning how each year of the decomposed lines was worked out.
+pub(crate) fn traced_copyright_lines(
+    options: impl YearRangeNormalizationOptions + Copy,
+) -> impl FnMut(&str) -> IResult<&str, (Copyright, Vec<TracedYearSpec>)> {
     move |input: &str| {
-        alt((
-            map(
-                many1(terminated(copyright_line(options), multispace0)),
-                |mut parsed| {
-                    if parsed.len() == 1 {
-                        Copyright::Decomposable(parsed.pop().expect("always there"))
-                    } else {
-                        Copyright::MultilineDecomposable(parsed)
-                    }
-                },
-            ),
-            map(
-                terminated(copyright_line(options), tuple((multispace0, eof))),
-                Copyright::Decomposable,
-            ),
-            map(preceded(multispace0, rest), |s: &str| {
-                Copyright::Complex(s.trim().to_string())
-            }),
-        ))(input)
+        let mut decomposed = vec![];
+        let mut years = vec![];
+        let mut residue = vec![];
+        for line in input.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            match parse_traced_line(options, line) {
+                Some((parsed, traced)) => {
+                    decomposed.push(parsed);
+                    years.extend(traced);
+                }
+                None => residue.push(line.to_string()),
+            }
+        }
+        if decomposed.is_empty() {
+            // Keep the text as it was
+            return Ok(("", (Copyright::Complex(input.trim().to_string()), years)));
+        }
+        Ok(("", (Copyright::from_parts(decomposed, residue), years)))
     }
 }
 
@@ -132,9 +420,9 @@ mod tests {
     use super::*;
     use crate::{
         raw_year::{options::YearRangeNormalization, traits::SetYearRangeNormalizationOptions},
-        years::{Year, YearSpec},
+        years::{OpenRangeForm, OpenRangeStyle, Year, YearSpec},
     };
-    use nom::{combinator::all_consuming, Finish};
+    use nom::Finish;
 
     #[test]
     fn parse_year_spec() {
@@ -144,7 +432,20 @@ mod tests {
             all_year_spec_configured("2022").finish().unwrap().1,
             YearSpec::SingleYear(Year(2022))
         );
-        assert!(all_year_spec_configured("2022-").finish().is_err());
+        // Open-ended, resolved against the reference year
+        assert_eq!(
+            all_consuming(year_spec(
+                YearRangeNormalization::default().reference_year(2025)
+            ))("2022-")
+            .finish()
+            .unwrap()
+            .1,
+            YearSpec::OpenRange(OpenYearRange::new(
+                Year(2022),
+                Year(2025),
+                OpenRangeForm::Suffix("-".to_string())
+            ))
+        );
 
         assert!(all_year_spec_configured("1995-20").finish().is_err());
         assert_eq!(
@@ -182,7 +483,15 @@ mod tests {
             all_consuming(year_spec_vec(opt()))("2022").unwrap().1,
             vec![YearSpec::SingleYear(Year(2022))]
         );
-        assert!(all_consuming(year_spec_vec(opt()))("2022-").is_err());
+        assert_eq!(
+            all_consuming(year_spec_vec(opt().reference_year(2025)))("2019, 2022-present")
+                .unwrap()
+                .1
+                .iter()
+                .map(|y| y.to_string_with(OpenRangeStyle::Resolved))
+                .collect::<Vec<_>>(),
+            vec!["2019", "2022-2025"]
+        );
         assert!(all_consuming(year_spec_vec(opt()))("1995-1821").is_err());
 
         assert!(all_consuming(year_spec_vec(opt()))("1995-20")
@@ -249,6 +558,132 @@ mod tests {
         );
     }
 
+    #[test]
+    fn century_window_and_bounds() {
+        let parse = |options, input| {
+            all_consuming(year_spec_vec(options))(input)
+                .finish()
+                .map(|(_, years)| years)
+                .ok()
+        };
+        let default = YearRangeNormalization {
+            allow_century_guess: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            parse(default, "55"),
+            Some(vec![YearSpec::SingleYear(Year(2055))])
+        );
+        assert_eq!(
+            parse(default, "98-03"),
+            None,
+            "needs allow_assuming_y2k_span"
+        );
+        assert_eq!(parse(default, "1850"), None);
+        assert_eq!(parse(default, "2100"), None);
+
+        // A 1950s-era codebase: two-digit years are at most 5 years after 1960
+        let fifties = YearRangeNormalization {
+            reference_year: Some(1960),
+            two_digit_year_pivot: Some(5),
+            earliest_year: Some(1800),
+            ..default
+        }
+        .allow_assuming_y2k_span(true);
+        assert_eq!(
+            parse(fifties, "55"),
+            Some(vec![YearSpec::SingleYear(Year(1955))])
+        );
+        assert_eq!(
+            parse(fifties, "52-57, 1850"),
+            Some(vec![
+                YearSpec::ClosedRange(YearRange::new(Year(1952), Year(1957))),
+                YearSpec::SingleYear(Year(1850))
+            ])
+        );
+        // The window is 1866-1965, so this spans 1900
+        assert_eq!(
+            parse(fifties, "98-03"),
+            Some(vec![YearSpec::ClosedRange(YearRange::new(
+                Year(1898),
+                Year(1903)
+            ))])
+        );
+
+        // Files dated in the 22nd century
+        let future = YearRangeNormalization {
+            reference_year: Some(2100),
+            two_digit_year_pivot: Some(10),
+            latest_year: Some(2199),
+            ..default
+        };
+        assert_eq!(
+            parse(future, "2100, 05"),
+            Some(vec![
+                YearSpec::SingleYear(Year(2100)),
+                YearSpec::SingleYear(Year(2105))
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_broader_year_syntax() {
+        let opt = YearRangeNormalization::default;
+        let parse = |options, input| {
+            all_consuming(year_spec_vec(options))(input)
+                .finish()
+                .map(|(_, years)| years)
+        };
+
+        assert_eq!(
+            parse(opt(), "2019\u{2013}2021").unwrap(),
+            vec![YearSpec::range(Year(2019), Year(2021))]
+        );
+        assert_eq!(
+            parse(opt(), "2019,2020").unwrap(),
+            vec![YearSpec::single(2019), YearSpec::single(2020)]
+        );
+        assert_eq!(
+            parse(opt(), "'98, 2004").unwrap(),
+            vec![YearSpec::single(1998), YearSpec::single(2004)]
+        );
+        assert_eq!(
+            parse(opt(), "2021-03-04").unwrap(),
+            vec![YearSpec::single(2021)]
+        );
+
+        // Slashes: a list unless ranges are allowed
+        assert_eq!(
+            parse(opt(), "2019/2021").unwrap(),
+            vec![YearSpec::single(2019), YearSpec::single(2021)]
+        );
+        assert_eq!(
+            parse(opt().allow_slash_ranges(true), "2019/2021").unwrap(),
+            vec![YearSpec::range(Year(2019), Year(2021))]
+        );
+
+        // Bare two-digit years may be required to have an apostrophe
+        let strict = opt().require_apostrophe_for_two_digit_years(true);
+        assert!(parse(strict, "98").is_err());
+        assert_eq!(parse(strict, "'98").unwrap(), vec![YearSpec::single(1998)]);
+        assert_eq!(
+            parse(strict, "1998-99").unwrap(),
+            vec![YearSpec::range(Year(1998), Year(1999))]
+        );
+        assert_eq!(parse(opt(), "98").unwrap(), vec![YearSpec::single(1998)]);
+
+        assert_eq!(
+            all_consuming(copyright_line(opt()))("Copyright (c) 2019,2020, Jane Doe")
+                .finish()
+                .unwrap()
+                .1,
+            DecomposedCopyright::new(
+                &[YearSpec::single(2019), YearSpec::single(2020)],
+                "Jane Doe"
+            )
+        );
+    }
+
     #[test]
     fn test_line() {
         let opt = YearRangeNormalization::default;
@@ -268,4 +703,139 @@ mod tests {
             DecomposedCopyright::new_from_single_yearspec(&YearSpec::single(2024), "Rylie Pavlik")
         );
     }
+
+    #[test]
+    fn test_prefixes() {
+        let opt = YearRangeNormalization::default;
+        let expected =
+            DecomposedCopyright::new_from_single_yearspec(&YearSpec::single(2024), "Rylie Pavlik");
+        for line in [
+            "Copyright (C) 2024, Rylie Pavlik",
+            "copyright (c) 2024, Rylie Pavlik",
+            "© 2024, Rylie Pavlik",
+            "©2024, Rylie Pavlik",
+            "Copyright © 2024, Rylie Pavlik",
+            "(c) 2024, Rylie Pavlik",
+            "Copr. 2024, Rylie Pavlik",
+            "Copyright (c) 2024 by Rylie Pavlik",
+            "Copyright 2024, by Rylie Pavlik",
+            "SPDX-FileCopyrightText: 2024, Rylie Pavlik",
+            "SPDX-FileCopyrightText: Copyright (C) 2024, Rylie Pavlik",
+            "Portions Copyright (C) 2024, Rylie Pavlik",
+            "Copyright: Copyright 2024, Rylie Pavlik",
+            "Copyright: 2024, Rylie Pavlik",
+        ] {
+            assert_eq!(
+                all_consuming(copyright_line(opt()))(line)
+                    .finish()
+                    .unwrap()
+                    .1,
+                expected,
+                "{line}"
+            );
+        }
+
+        // Not a prefix by itself
+        assert!(
+            all_consuming(copyright_line(opt()))("Portions 2024, Rylie Pavlik")
+                .finish()
+                .is_err()
+        );
+        // Holder names that just happen to start like a prefix are kept intact
+        assert_eq!(
+            all_consuming(copyright_line(opt()))("2024, Coprocessor Inc.")
+                .finish()
+                .unwrap()
+                .1,
+            DecomposedCopyright::new_from_single_yearspec(
+                &YearSpec::single(2024),
+                "Coprocessor Inc."
+            )
+        );
+    }
+
+    #[test]
+    fn test_holder_first() {
+        let opt = YearRangeNormalization::default;
+        let parse = |line| {
+            all_consuming(copyright_line(opt()))(line)
+                .finish()
+                .map(|(_, parsed)| parsed)
+        };
+        assert_eq!(
+            parse("Copyright Jane Doe 2019-2021").unwrap(),
+            DecomposedCopyright::new_from_single_yearspec(
+                &YearSpec::range(Year(2019), Year(2021)),
+                "Jane Doe"
+            )
+        );
+        let foo_2004 = DecomposedCopyright::new_from_single_yearspec(
+            &YearSpec::single(2004),
+            "The Foo Project",
+        );
+        assert_eq!(
+            parse("Copyright (C) The Foo Project, 2004").unwrap(),
+            foo_2004
+        );
+        assert_eq!(parse("The Foo Project, 2004.").unwrap(), foo_2004);
+        assert_eq!(parse("The Foo Project (2004)").unwrap(), foo_2004);
+        assert_eq!(
+            parse("Copyright Foo 3D, Inc. 2004, 2006-2008").unwrap(),
+            DecomposedCopyright::new(
+                &[
+                    YearSpec::single(2004),
+                    YearSpec::range(Year(2006), Year(2008))
+                ],
+                "Foo 3D, Inc."
+            )
+        );
+        // Two-digit years still follow the normalization options
+        assert!(parse("Jane Doe 1995-20").is_err());
+        assert_eq!(
+            all_consuming(copyright_line(
+                opt().allow_mixed_size_implied_century_rollover(true)
+            ))("Jane Doe 1995-20")
+            .finish()
+            .unwrap()
+            .1,
+            DecomposedCopyright::new_from_single_yearspec(
+                &YearSpec::range(Year(1995), Year(2020)),
+                "Jane Doe"
+            )
+        );
+        // Need an actual holder
+        assert!(parse("2004 2005").is_err());
+    }
+
+    #[test]
+    fn year_provenance() {
+        let options = YearRangeNormalization {
+            allow_century_guess: true,
+            allow_assuming_y2k_span: true,
+            ..Default::default()
+        };
+        let (copyright, years) = Copyright::try_parse_with_provenance(
+            options,
+            "Copyright 1998-99, 2005, Jane Doe\nCopyright 98-03, Foo Corp.\nCopyright 96-97, 2001-04, Bar\nAll rights reserved.",
+        )
+        .unwrap();
+        assert_eq!(copyright.decomposed_lines().len(), 3);
+        assert_eq!(
+            years.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec![
+                "1998-1999: century taken from the other end of the range",
+                "2005: written with four digits",
+                "1998-2003: assumed to span the turn of the century",
+                "1996-1997: century guessed",
+                "2001-2004: century taken from the other end of the range",
+            ]
+        );
+        assert_eq!(
+            years
+                .iter()
+                .map(|year| year.provenance.is_heuristic())
+                .collect::<Vec<_>>(),
+            vec![true, false, true, true, true]
+        );
+    }
 }
@@ -0,0 +1,490 @@
+// Copyright 2025, Collabora, Ltd.
+//
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Explaining why a copyright statement, or a line of it, could not be decomposed.
+
+use std::{fmt::Display, ops::Range};
+
+use nom::combinator::consumed;
+
+use crate::{
+    copyright_parsing::{has_copyright_prefix, parse_line, raw_range_to_year_spec},
+    raw_year::{
+        self,
+        options::YearRangeNormalization,
+        traits::{
+            SetSingleYearNormalizationOptions, SetYearRangeNormalizationOptions,
+            SingleYearNormalizationOptions, YearRangeNormalizationOptions,
+        },
+        YearExpr,
+    },
+    years::Year,
+};
+
+/// Why a line of a copyright statement could not be decomposed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CopyrightErrorReason {
+    /// A year range ends before it begins: "2022-2019"
+    RangeEndBeforeBegin,
+    /// A two-digit year would need its century guessed: "98"
+    CenturyGuessNeeded,
+    /// A range of two-digit years would need to be assumed to span Y2K: "98-03"
+    Y2kSpanNeeded,
+    /// A range would need its end to roll over into the next century: "1998-03"
+    CenturyRolloverNeeded,
+    /// A two-digit year standing alone is not written with an apostrophe: "98" rather than "'98"
+    ApostropheRequired,
+    /// A year is before the earliest year we recognize: "1850"
+    YearBeforeEarliest,
+    /// A year is after the latest year we recognize: "2150"
+    YearAfterLatest,
+    /// Starts like a copyright statement, but we could not make sense of the rest
+    Unrecognized,
+}
+
+impl CopyrightErrorReason {
+    /// A stable, machine-readable code for this reason
+    pub fn code(self) -> &'static str {
+        match self {
+            CopyrightErrorReason::RangeEndBeforeBegin => "range-end-before-begin",
+            CopyrightErrorReason::CenturyGuessNeeded => "century-guess-needed",
+            CopyrightErrorReason::Y2kSpanNeeded => "y2k-span-needed",
+            CopyrightErrorReason::CenturyRolloverNeeded => "century-rollover-needed",
+            CopyrightErrorReason::ApostropheRequired => "apostrophe-required",
+            CopyrightErrorReason::YearBeforeEarliest => "year-before-earliest",
+            CopyrightErrorReason::YearAfterLatest => "year-after-latest",
+            CopyrightErrorReason::Unrecognized => "unrecognized",
+        }
+    }
+
+    /// The `YearRangeNormalization` field that, toggled, would have made the input parse
+    pub fn option_name(self) -> Option<&'static str> {
+        match self {
+            CopyrightErrorReason::CenturyGuessNeeded => Some("allow_century_guess"),
+            CopyrightErrorReason::Y2kSpanNeeded => Some("allow_assuming_y2k_span"),
+            CopyrightErrorReason::CenturyRolloverNeeded => {
+                Some("allow_mixed_size_implied_century_rollover")
+            }
+            CopyrightErrorReason::ApostropheRequired => {
+                Some("require_apostrophe_for_two_digit_years")
+            }
+            CopyrightErrorReason::YearBeforeEarliest => Some("earliest_year"),
+            CopyrightErrorReason::YearAfterLatest => Some("latest_year"),
+            CopyrightErrorReason::RangeEndBeforeBegin | CopyrightErrorReason::Unrecognized => None,
+        }
+    }
+
+    /// How to change the command line to make the input parse, if that would help
+    pub fn suggestion(self) -> Option<&'static str> {
+        match self {
+            CopyrightErrorReason::CenturyGuessNeeded => Some("pass --allow-century-guess"),
+            CopyrightErrorReason::Y2kSpanNeeded => Some("pass --allow-assuming-y2k-span"),
+            CopyrightErrorReason::CenturyRolloverNeeded => {
+                Some("pass --allow-mixed-size-implied-century-rollover")
+            }
+            CopyrightErrorReason::ApostropheRequired => {
+                Some("omit --require-apostrophe-for-two-digit-years")
+            }
+            CopyrightErrorReason::YearBeforeEarliest => Some("pass an earlier --earliest-year"),
+            CopyrightErrorReason::YearAfterLatest => Some("pass a later --latest-year"),
+            CopyrightErrorReason::RangeEndBeforeBegin | CopyrightErrorReason::Unrecognized => None,
+        }
+    }
+}
+
+impl Display for CopyrightErrorReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CopyrightErrorReason::RangeEndBeforeBegin => {
+                write!(f, "year range ends before it begins")
+            }
+            CopyrightErrorReason::CenturyGuessNeeded => {
+                write!(f, "two-digit year needs its century guessed")
+            }
+            CopyrightErrorReason::Y2kSpanNeeded => {
+                write!(f, "two-digit year range needs to be assumed to span Y2K")
+            }
+            CopyrightErrorReason::CenturyRolloverNeeded => {
+                write!(f, "year range end needs to roll over into the next century")
+            }
+            CopyrightErrorReason::ApostropheRequired => {
+                write!(f, "two-digit year is not written with an apostrophe")
+            }
+            CopyrightErrorReason::YearBeforeEarliest => {
+                write!(f, "year is before the earliest year recognized")
+            }
+            CopyrightErrorReason::YearAfterLatest => {
+                write!(f, "year is after the latest year recognized")
+            }
+            CopyrightErrorReason::Unrecognized => write!(f, "could not decompose copyright line"),
+        }
+    }
+}
+
+/// A line of a copyright statement that could not be decomposed, and why.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub struct CopyrightDecompositionError {
+    pub reason: CopyrightErrorReason,
+    /// Byte offsets of the offending text within the whole statement
+    pub span: Range<usize>,
+    /// Line number within the statement, starting at 1
+    pub line_number: usize,
+    /// Column (in characters) of the start of the span within its line, starting at 1
+    pub column: usize,
+    /// The offending line, trimmed
+    pub line: String,
+}
+
+impl CopyrightDecompositionError {
+    /// Create an error for a span of the statement, working out the line and column.
+    pub(crate) fn new(statement: &str, reason: CopyrightErrorReason, span: Range<usize>) -> Self {
+        let before = &statement[..span.start];
+        let line_start = before.rfind('\n').map_or(0, |pos| pos + 1);
+        let line_end = statement[line_start..]
+            .find('\n')
+            .map_or(statement.len(), |pos| line_start + pos);
+        Self {
+            reason,
+            line_number: before.matches('\n').count() + 1,
+            column: statement[line_start..span.start].chars().count() + 1,
+            line: statement[line_start..line_end].trim().to_string(),
+            span,
+        }
+    }
+
+    pub(crate) fn from_nom(statement: &str, error: nom::error::Error<&str>) -> Self {
+        let start = statement.len() - error.input.len();
+        let end = statement[start..]
+            .find('\n')
+            .map_or(statement.len(), |pos| start + pos);
+        Self::new(statement, CopyrightErrorReason::Unrecognized, start..end)
+    }
+}
+
+impl Display for CopyrightDecompositionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Failed decomposing copyright at line {}, column {}: {} [{}]: {}",
+            self.line_number,
+            self.column,
+            self.reason,
+            self.reason.code(),
+            self.line
+        )?;
+        if let Some(suggestion) = self.reason.suggestion() {
+            write!(f, " (to accept this, {suggestion})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Text that could not be parsed as years.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum YearParseError {
+    /// Not valid years with these options
+    Invalid {
+        text: String,
+        reason: CopyrightErrorReason,
+    },
+    /// Valid years, but not the kind expected: an open-ended range where a closed range was needed
+    UnexpectedForm {
+        text: String,
+        expected: &'static str,
+    },
+}
+
+impl YearParseError {
+    pub(crate) fn invalid(text: &str, reason: CopyrightErrorReason) -> Self {
+        Self::Invalid {
+            text: text.to_string(),
+            reason,
+        }
+    }
+
+    pub(crate) fn unexpected_form(text: &str, expected: &'static str) -> Self {
+        Self::UnexpectedForm {
+            text: text.to_string(),
+            expected,
+        }
+    }
+
+    /// The text that could not be parsed
+    pub fn text(&self) -> &str {
+        match self {
+            YearParseError::Invalid { text, .. } | YearParseError::UnexpectedForm { text, .. } => {
+                text
+            }
+        }
+    }
+
+    /// Why the text was not valid years, if it was not
+    pub fn reason(&self) -> Option<CopyrightErrorReason> {
+        match self {
+            YearParseError::Invalid { reason, .. } => Some(*reason),
+            YearParseError::UnexpectedForm { .. } => None,
+        }
+    }
+}
+
+impl Display for YearParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            YearParseError::Invalid {
+                text,
+                reason: CopyrightErrorReason::Unrecognized,
+            } => write!(f, "Could not parse \"{text}\" as years"),
+            YearParseError::Invalid { text, reason } => {
+                write!(
+                    f,
+                    "Could not parse \"{text}\" as years: {reason} [{}]",
+                    reason.code()
+                )?;
+                if let Some(suggestion) = reason.suggestion() {
+                    write!(f, " (to accept this, {suggestion})")?;
+                }
+                Ok(())
+            }
+            YearParseError::UnexpectedForm { text, expected } => {
+                write!(f, "Expected {expected}, but found \"{text}\"")
+            }
+        }
+    }
+}
+
+/// A year range whose end is before its beginning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("Year range {begin}-{end} ends before it begins")]
+pub struct InvalidYearRangeError {
+    pub begin: Year,
+    pub end: Year,
+}
+
+/// Find the years and year ranges in a line, with their byte offsets.
+fn year_tokens(line: &str) -> Vec<(Range<usize>, (YearExpr, YearExpr))> {
+    let mut tokens = vec![];
+    let mut pos = 0;
+    let mut prev: Option<char> = None;
+    while let Some(c) = line[pos..].chars().next() {
+        let rest = &line[pos..];
+        let at_word_start = !prev.is_some_and(char::is_alphanumeric);
+        if at_word_start && (c.is_ascii_digit() || raw_year::parse::APOSTROPHES.contains(&c)) {
+            if let Ok((after, (text, pair))) = consumed(raw_year::parse::year_spec)(rest) {
+                if !after.starts_with(|c: char| c.is_ascii_digit()) {
+                    tokens.push((pos..pos + text.len(), pair));
+                    pos += text.len();
+                    prev = text.chars().last();
+                    continue;
+                }
+            }
+        }
+        pos += c.len_utf8();
+        prev = Some(c);
+    }
+    tokens
+}
+
+/// Each option that could be relaxed, with the reason to give if doing so makes a line parse.
+fn relaxations(
+    options: YearRangeNormalization,
+) -> Vec<(CopyrightErrorReason, YearRangeNormalization)> {
+    let mut relaxed = vec![];
+    if !options.allow_century_guess {
+        relaxed.push((
+            CopyrightErrorReason::CenturyGuessNeeded,
+            options.allow_century_guess(true),
+        ));
+    }
+    if !options.allow_assuming_y2k_span {
+        relaxed.push((
+            CopyrightErrorReason::Y2kSpanNeeded,
+            options.allow_assuming_y2k_span(true),
+        ));
+    }
+    if !options.allow_mixed_size_implied_century_rollover {
+        relaxed.push((
+            CopyrightErrorReason::CenturyRolloverNeeded,
+            options.allow_mixed_size_implied_century_rollover(true),
+        ));
+    }
+    if options.require_apostrophe_for_two_digit_years {
+        relaxed.push((
+            CopyrightErrorReason::ApostropheRequired,
+            options.require_apostrophe_for_two_digit_years(false),
+        ));
+    }
+    // Any four-digit number is a candidate year, within these
+    let (earliest, latest) = options.get_four_digit_year_bounds();
+    if earliest > 1000 {
+        relaxed.push((
+            CopyrightErrorReason::YearBeforeEarliest,
+            YearRangeNormalization {
+                earliest_year: Some(1000),
+                ..options
+            },
+        ));
+    }
+    if latest < 9999 {
+        relaxed.push((
+            CopyrightErrorReason::YearAfterLatest,
+            YearRangeNormalization {
+                latest_year: Some(9999),
+                ..options
+            },
+        ));
+    }
+    relaxed
+}
+
+/// Work out why a trimmed line could not be decomposed, and which part of it is at fault.
+///
+/// Returns `None` if it can be decomposed, or if it does not look like a copyright statement at all.
+fn diagnose_line(
+    options: YearRangeNormalization,
+    line: &str,
+) -> Option<(CopyrightErrorReason, Range<usize>)> {
+    if parse_line(options, line).is_some() {
+        return None;
+    }
+    let tokens = year_tokens(line);
+    for (reason, relaxed) in relaxations(options) {
+        if parse_line(relaxed, line).is_none() {
+            continue;
+        }
+        let span = tokens
+            .iter()
+            .find(|(span, pair)| {
+                if reason == CopyrightErrorReason::ApostropheRequired {
+                    pair.0 == pair.1
+                        && matches!(pair.0, YearExpr::TwoDigit(_))
+                        && !line[span.clone()].starts_with(raw_year::parse::APOSTROPHES)
+                } else {
+                    raw_range_to_year_spec(options, *pair).is_none()
+                        && raw_range_to_year_spec(relaxed, *pair).is_some()
+                }
+            })
+            .map_or(0..line.len(), |(span, _)| span.clone());
+        return Some((reason, span));
+    }
+    let reversed = tokens.iter().find(
+        |(_, pair)| matches!(pair, (YearExpr::FourDigit(b), YearExpr::FourDigit(e)) if b > e),
+    );
+    if let Some((span, _)) = reversed {
+        return Some((CopyrightErrorReason::RangeEndBeforeBegin, span.clone()));
+    }
+    if has_copyright_prefix(line) {
+        return Some((CopyrightErrorReason::Unrecognized, 0..line.len()));
+    }
+    None
+}
+
+/// Work out why some text could not be parsed as years, given a function that tries parsing it.
+pub(crate) fn diagnose_years(
+    options: impl YearRangeNormalizationOptions + Copy,
+    text: &str,
+    parses: impl Fn(YearRangeNormalization, &str) -> bool,
+) -> CopyrightErrorReason {
+    let options = YearRangeNormalization::from_options(options);
+    relaxations(options)
+        .into_iter()
+        .find(|(_, relaxed)| parses(*relaxed, text))
+        .map(|(reason, _)| reason)
+        .or_else(|| {
+            year_tokens(text)
+                .iter()
+                .any(|(_, pair)| {
+                    matches!(pair, (YearExpr::FourDigit(b), YearExpr::FourDigit(e)) if b > e)
+                })
+                .then_some(CopyrightErrorReason::RangeEndBeforeBegin)
+        })
+        .unwrap_or(CopyrightErrorReason::Unrecognized)
+}
+
+/// Explain each line of a statement that looks like a copyright statement but could not be decomposed.
+pub(crate) fn diagnose(
+    options: impl YearRangeNormalizationOptions + Copy,
+    statement: &str,
+) -> Vec<CopyrightDecompositionError> {
+    let options = YearRangeNormalization::from_options(options);
+    let mut errors = vec![];
+    let mut line_offset = 0;
+    for raw_line in statement.split_inclusive('\n') {
+        let line = raw_line.trim();
+        let start = line_offset + (raw_line.len() - raw_line.trim_start().len());
+        line_offset += raw_line.len();
+        if let Some((reason, span)) = diagnose_line(options, line) {
+            errors.push(CopyrightDecompositionError::new(
+                statement,
+                reason,
+                start + span.start..start + span.end,
+            ));
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diagnose, CopyrightErrorReason};
+    use crate::raw_year::{
+        options::YearRangeNormalization,
+        traits::{SetSingleYearNormalizationOptions, SetYearRangeNormalizationOptions},
+    };
+
+    #[test]
+    fn reasons() {
+        let options = YearRangeNormalization::default();
+
+        let errors = diagnose(options, "Copyright 2022-2019, Jane Doe");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].reason, CopyrightErrorReason::RangeEndBeforeBegin);
+        assert_eq!(errors[0].span, 10..19);
+        assert_eq!(errors[0].column, 11);
+
+        let errors = diagnose(options, "All rights reserved.\n  Copyright 97-98, Jane Doe");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].reason, CopyrightErrorReason::CenturyGuessNeeded);
+        assert_eq!(errors[0].line_number, 2);
+        assert_eq!(errors[0].column, 13);
+        assert_eq!(errors[0].line, "Copyright 97-98, Jane Doe");
+        assert_eq!(errors[0].reason.option_name(), Some("allow_century_guess"));
+        assert!(errors[0].to_string().contains("--allow-century-guess"));
+
+        let errors = diagnose(
+            options.allow_century_guess(true),
+            "Copyright 98-03, Jane Doe",
+        );
+        assert_eq!(errors[0].reason, CopyrightErrorReason::Y2kSpanNeeded);
+        assert_eq!(errors[0].span, 10..15);
+
+        let errors = diagnose(
+            options.require_apostrophe_for_two_digit_years(true),
+            "Copyright 98, Jane Doe",
+        );
+        assert_eq!(errors[0].reason, CopyrightErrorReason::ApostropheRequired);
+        assert_eq!(errors[0].span, 10..12);
+
+        let errors = diagnose(options, "Copyright 1850-1860, Jane Doe");
+        assert_eq!(errors[0].reason, CopyrightErrorReason::YearBeforeEarliest);
+        assert_eq!(errors[0].span, 10..19);
+        let errors = diagnose(options, "Copyright 2150, Jane Doe");
+        assert_eq!(errors[0].reason, CopyrightErrorReason::YearAfterLatest);
+        assert_eq!(errors[0].span, 10..14);
+
+        let errors = diagnose(options, "Copyright 2020, 2021 ~~~");
+        assert_eq!(errors[0].reason, CopyrightErrorReason::Unrecognized);
+        assert_eq!(errors[0].reason.code(), "unrecognized");
+    }
+
+    #[test]
+    fn no_errors() {
+        let options = YearRangeNormalization::default();
+        assert!(diagnose(
+            options,
+            "Copyright 2019-2022, Jane Doe\nAll rights reserved."
+        )
+        .is_empty());
+        assert!(diagnose(options, "Some free text").is_empty());
+    }
+}
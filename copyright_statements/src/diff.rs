@@ -0,0 +1,193 @@
+// Copyright 2025, Collabora, Ltd.
+//
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::fmt::Display;
+
+use itertools::Itertools;
+
+use crate::{CoalescingPolicy, Copyright, DecomposedCopyright, Holder, YearRange};
+
+/// One difference between an old and a new copyright statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CopyrightChange {
+    /// A holder only in the new statement, with all of their years
+    HolderAdded(DecomposedCopyright),
+    /// A holder only in the old statement, with all of their years
+    HolderRemoved(DecomposedCopyright),
+    /// Years added for a holder in both statements
+    YearsExtended {
+        holder: Holder,
+        years: Vec<YearRange>,
+    },
+    /// Years dropped for a holder in both statements
+    YearsRemoved {
+        holder: Holder,
+        years: Vec<YearRange>,
+    },
+    /// A line of free text only in the new statement
+    TextAdded(String),
+    /// A line of free text only in the old statement
+    TextRemoved(String),
+}
+
+impl Display for CopyrightChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CopyrightChange::HolderAdded(line) => write!(f, "holder added: {line}"),
+            CopyrightChange::HolderRemoved(line) => write!(f, "holder removed: {line}"),
+            CopyrightChange::YearsExtended { holder, years } => {
+                write!(
+                    f,
+                    "years extended for {holder}: {}",
+                    years.iter().join(", ")
+                )
+            }
+            CopyrightChange::YearsRemoved { holder, years } => {
+                write!(f, "years removed for {holder}: {}", years.iter().join(", "))
+            }
+            CopyrightChange::TextAdded(line) => write!(f, "text added: {line}"),
+            CopyrightChange::TextRemoved(line) => write!(f, "text removed: {line}"),
+        }
+    }
+}
+
+/// The changes from an old to a new copyright statement, as returned by `Copyright::diff`.
+///
+/// Displays as a report with one change per line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CopyrightDiff {
+    pub changes: Vec<CopyrightChange>,
+}
+
+impl CopyrightDiff {
+    /// Are the two statements equivalent?
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl Display for CopyrightDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn diff(old: &Copyright, new: &Copyright) -> CopyrightDiff {
+    let mut changes = vec![];
+    let identities = Copyright::holder_identities([old, new]);
+    let mut new_holders = new.years_per_holder_with(&identities);
+
+    for (holder, old_years) in old.years_per_holder_with(&identities) {
+        let key = identities.resolve(&holder);
+        let Some(position) = new_holders
+            .iter()
+            .position(|(new_holder, _)| identities.resolve(new_holder) == key)
+        else {
+            changes.push(CopyrightChange::HolderRemoved(DecomposedCopyright {
+                years: old_years.into_coalesced_specs(CoalescingPolicy::Strict),
+                holder,
+            }));
+            continue;
+        };
+        let (mut new_holder, new_years) = new_holders.remove(position);
+        new_holder.merge(&holder);
+        let holder = new_holder;
+        let added = new_years.clone().subtract(old_years.clone());
+        let removed = old_years.subtract(new_years);
+        if !added.is_empty() {
+            changes.push(CopyrightChange::YearsExtended {
+                holder: holder.clone(),
+                years: added,
+            });
+        }
+        if !removed.is_empty() {
+            changes.push(CopyrightChange::YearsRemoved {
+                holder,
+                years: removed,
+            });
+        }
+    }
+    changes.extend(new_holders.into_iter().map(|(holder, years)| {
+        CopyrightChange::HolderAdded(DecomposedCopyright {
+            years: years.into_coalesced_specs(CoalescingPolicy::Strict),
+            holder,
+        })
+    }));
+
+    let old_residue = old.residue_lines();
+    let new_residue = new.residue_lines();
+    changes.extend(
+        old_residue
+            .iter()
+            .filter(|line| !new_residue.contains(line))
+            .unique()
+            .map(|line| CopyrightChange::TextRemoved(line.to_string())),
+    );
+    changes.extend(
+        new_residue
+            .iter()
+            .filter(|line| !old_residue.contains(line))
+            .unique()
+            .map(|line| CopyrightChange::TextAdded(line.to_string())),
+    );
+    CopyrightDiff { changes }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Copyright, CopyrightChange, Holder, Year, YearRange, YearRangeNormalization};
+
+    fn parse(statement: &str) -> Copyright {
+        Copyright::try_parse(YearRangeNormalization::default(), statement).unwrap()
+    }
+
+    #[test]
+    fn union_and_difference() {
+        let old = parse("Copyright 2018-2020, Jane Doe\nCopyright 2019, Example Corp.");
+        let new = parse("Copyright 2020-2023, Jane Doe <jane@example.com>\nCopyright 2022, Bob");
+
+        assert_eq!(
+            old.union(&new).to_string(),
+            "2022, Bob\n2019, Example Corp.\n2018-2023, Jane Doe <jane@example.com>"
+        );
+        assert_eq!(
+            old.difference(&new).unwrap().to_string(),
+            "2018-2019, Jane Doe\n2019, Example Corp."
+        );
+        assert_eq!(
+            new.difference(&old).unwrap().to_string(),
+            "2021-2023, Jane Doe <jane@example.com>\n2022, Bob"
+        );
+        assert!(old.difference(&old.union(&new)).is_none());
+    }
+
+    #[test]
+    fn diff_report() {
+        let old = parse(
+            "Copyright 2018-2020, Jane Doe\nCopyright 2019, Example Corp.\nAll rights reserved.",
+        );
+        let new = parse("Copyright 2019-2023, Jane Doe\nCopyright 2022, Bob");
+
+        let diff = old.diff(&new);
+        assert_eq!(
+            diff.changes[0],
+            CopyrightChange::YearsExtended {
+                holder: Holder::new("Jane Doe"),
+                years: vec![YearRange::new(Year(2021), Year(2023))]
+            }
+        );
+        assert_eq!(
+            diff.to_string(),
+            "years extended for Jane Doe: 2021-2023\n\
+             years removed for Jane Doe: 2018\n\
+             holder removed: 2019, Example Corp.\n\
+             holder added: 2022, Bob\n\
+             text removed: All rights reserved.\n"
+        );
+        assert!(new.diff(&new.clone().normalize()).is_empty());
+    }
+}
@@ -0,0 +1,303 @@
+// Copyright 2025, Collabora, Ltd.
+//
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::str::FromStr;
+
+use itertools::Itertools;
+
+use crate::{
+    holder::ContactStyle,
+    years::{
+        CoalescingPolicy, OpenRangeStyle, OpenYearRange, YearRange, YearRangeCollection, YearSpec,
+    },
+    Copyright, DecomposedCopyright,
+};
+
+/// What to put before each decomposed copyright line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CopyrightPrefix {
+    /// Nothing, as in Debian copyright files: "2019 Jane Doe"
+    #[default]
+    None,
+    /// "Copyright (C) 2019 Jane Doe"
+    Copyright,
+    /// The REUSE form: "SPDX-FileCopyrightText: 2019 Jane Doe"
+    SpdxFileCopyrightText,
+}
+
+impl CopyrightPrefix {
+    fn as_str(&self) -> &'static str {
+        match self {
+            CopyrightPrefix::None => "",
+            CopyrightPrefix::Copyright => "Copyright (C) ",
+            CopyrightPrefix::SpdxFileCopyrightText => "SPDX-FileCopyrightText: ",
+        }
+    }
+}
+
+/// How to output the years of each line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum YearCompaction {
+    /// Every year and range: "2015, 2018-2020", merged according to `CopyrightFormatter::coalescing`
+    #[default]
+    List,
+    /// A single range from the first to the last year: "2015-2020"
+    Span,
+    /// No years at all
+    Omit,
+}
+
+/// In what order to output the decomposed lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HolderOrder {
+    /// In the order they appear in the statement
+    #[default]
+    AsWritten,
+    /// Sorted by holder name, ignoring case
+    Alphabetical,
+    /// Sorted by the earliest year, with lines without years last
+    Chronological,
+}
+
+/// The error returned when parsing an unknown name for a formatting option.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Unknown {option} \"{value}\", expected one of: {expected}")]
+pub struct UnknownFormatOptionError {
+    option: &'static str,
+    value: String,
+    expected: &'static str,
+}
+
+impl UnknownFormatOptionError {
+    pub(crate) fn new(option: &'static str, value: &str, expected: &'static str) -> Self {
+        Self {
+            option,
+            value: value.to_string(),
+            expected,
+        }
+    }
+}
+
+impl FromStr for CopyrightPrefix {
+    type Err = UnknownFormatOptionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(CopyrightPrefix::None),
+            "copyright" => Ok(CopyrightPrefix::Copyright),
+            "spdx" => Ok(CopyrightPrefix::SpdxFileCopyrightText),
+            _ => Err(UnknownFormatOptionError::new(
+                "copyright prefix",
+                s,
+                "none, copyright, spdx",
+            )),
+        }
+    }
+}
+
+impl FromStr for YearCompaction {
+    type Err = UnknownFormatOptionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "list" => Ok(YearCompaction::List),
+            "span" => Ok(YearCompaction::Span),
+            "omit" => Ok(YearCompaction::Omit),
+            _ => Err(UnknownFormatOptionError::new(
+                "year compaction",
+                s,
+                "list, span, omit",
+            )),
+        }
+    }
+}
+
+impl FromStr for HolderOrder {
+    type Err = UnknownFormatOptionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "as-written" => Ok(HolderOrder::AsWritten),
+            "alphabetical" => Ok(HolderOrder::Alphabetical),
+            "chronological" => Ok(HolderOrder::Chronological),
+            _ => Err(UnknownFormatOptionError::new(
+                "holder order",
+                s,
+                "as-written, alphabetical, chronological",
+            )),
+        }
+    }
+}
+
+/// Formats copyright statements in a selectable style.
+///
+/// The default matches `Display`: "2019-2021, Jane Doe", one holder per line.
+/// Lines of free text that could not be decomposed are output as written, after the decomposed lines.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CopyrightFormatter {
+    pub prefix: CopyrightPrefix,
+    /// Between the years and the holder
+    pub separator: String,
+    pub years: YearCompaction,
+    /// With `YearCompaction::List`, merge the years of each line with this policy,
+    /// or `None` to keep them as written
+    pub coalescing: Option<CoalescingPolicy>,
+    pub holder_order: HolderOrder,
+    pub contact_style: ContactStyle,
+    pub open_range_style: OpenRangeStyle,
+}
+
+impl Default for CopyrightFormatter {
+    fn default() -> Self {
+        Self {
+            prefix: CopyrightPrefix::default(),
+            separator: ", ".to_string(),
+            years: YearCompaction::default(),
+            coalescing: None,
+            holder_order: HolderOrder::default(),
+            contact_style: ContactStyle::default(),
+            open_range_style: OpenRangeStyle::default(),
+        }
+    }
+}
+
+/// A single spec from the first to the last year, keeping a trailing open range open.
+fn span(years: &[YearSpec]) -> Option<YearSpec> {
+    let (first, last) = (years.first()?, years.last()?);
+    let begin = YearRange::from(first.clone()).begin();
+    Some(match last {
+        YearSpec::OpenRange(open) => YearSpec::OpenRange(OpenYearRange::new(
+            begin,
+            open.resolved_end(),
+            open.form().clone(),
+        )),
+        _ => YearRange::new(begin, YearRange::from(last.clone()).end()).into(),
+    })
+}
+
+impl CopyrightFormatter {
+    /// Format a single decomposed line.
+    pub fn format_line(&self, line: &DecomposedCopyright) -> String {
+        let years = match self.years {
+            YearCompaction::List => match self.coalescing {
+                Some(policy) => {
+                    let mut collection = YearRangeCollection::new();
+                    collection.extend(line.years.iter().cloned());
+                    collection.into_coalesced_specs(policy)
+                }
+                None => line.years.clone(),
+            },
+            YearCompaction::Span => span(&line.years).into_iter().collect(),
+            YearCompaction::Omit => vec![],
+        };
+        let holder = line.holder.to_string_with(self.contact_style);
+        if years.is_empty() {
+            return format!("{}{holder}", self.prefix.as_str());
+        }
+        format!(
+            "{}{}{}{holder}",
+            self.prefix.as_str(),
+            years
+                .iter()
+                .map(|y| y.to_string_with(self.open_range_style))
+                .join(", "),
+            self.separator
+        )
+    }
+
+    /// Format a whole statement, one line per decomposed line, followed by any free text.
+    pub fn format(&self, copyright: &Copyright) -> String {
+        let lines = copyright.decomposed_lines().iter();
+        let lines = match self.holder_order {
+            HolderOrder::AsWritten => lines.collect_vec(),
+            HolderOrder::Alphabetical => lines
+                .sorted_by_cached_key(|line| line.holder.name_key())
+                .collect(),
+            HolderOrder::Chronological => lines
+                .sorted_by_key(|line| {
+                    line.years
+                        .iter()
+                        .map(|y| YearRange::from(y.clone()).begin())
+                        .min()
+                        .map_or((1, None), |year| (0, Some(year)))
+                })
+                .collect(),
+        };
+        lines
+            .into_iter()
+            .map(|line| self.format_line(line))
+            .chain(copyright.residue_lines().into_iter().map(String::from))
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        CoalescingPolicy, Copyright, CopyrightFormatter, CopyrightPrefix, HolderOrder,
+        YearCompaction, YearRangeNormalization,
+    };
+
+    #[test]
+    fn styles() {
+        let copyright = Copyright::try_parse(
+            YearRangeNormalization::default(),
+            "Copyright 2015, 2018-2020, Jane Doe\nCopyright Example Corp.\nCopyright 2010, Bob\nAll rights reserved.",
+        )
+        .unwrap();
+
+        let default = CopyrightFormatter::default();
+        assert_eq!(default.format(&copyright), copyright.to_string());
+
+        let debian = CopyrightFormatter {
+            separator: " ".to_string(),
+            years: YearCompaction::Span,
+            holder_order: HolderOrder::Chronological,
+            ..Default::default()
+        };
+        assert_eq!(
+            debian.format(&copyright),
+            "2010 Bob\n2015-2020 Jane Doe\nExample Corp.\nAll rights reserved."
+        );
+
+        let reuse = CopyrightFormatter {
+            prefix: CopyrightPrefix::SpdxFileCopyrightText,
+            separator: " ".to_string(),
+            holder_order: HolderOrder::Alphabetical,
+            ..Default::default()
+        };
+        assert_eq!(
+            reuse.format(&copyright),
+            "SPDX-FileCopyrightText: 2010 Bob\n\
+             SPDX-FileCopyrightText: Example Corp.\n\
+             SPDX-FileCopyrightText: 2015, 2018-2020 Jane Doe\n\
+             All rights reserved."
+        );
+
+        let names_only = CopyrightFormatter {
+            prefix: CopyrightPrefix::Copyright,
+            years: YearCompaction::Omit,
+            ..Default::default()
+        };
+        assert_eq!(
+            names_only.format_line(&copyright.decomposed_lines()[0]),
+            "Copyright (C) Jane Doe"
+        );
+
+        let bridged = CopyrightFormatter {
+            coalescing: Some(CoalescingPolicy::MaxGap(2)),
+            ..Default::default()
+        };
+        assert_eq!(
+            bridged.format_line(&copyright.decomposed_lines()[0]),
+            "2015-2020, Jane Doe"
+        );
+
+        assert_eq!("spdx".parse(), Ok(CopyrightPrefix::SpdxFileCopyrightText));
+        assert_eq!(
+            "sideways".parse::<HolderOrder>().unwrap_err().to_string(),
+            "Unknown holder order \"sideways\", expected one of: as-written, alphabetical, chronological"
+        );
+    }
+}
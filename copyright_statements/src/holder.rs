@@ -0,0 +1,511 @@
+// Copyright 2025, Collabora, Ltd.
+//
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use std::{collections::HashMap, fmt::Display};
+
+use itertools::Itertools;
+
+/// A copyright holder: a display name, with an optional e-mail address and URL.
+///
+/// Parsed from text like `Jane Doe <jane@example.org>` or `Foo, Inc. (https://example.com)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Holder {
+    pub name: String,
+    pub email: Option<String>,
+    pub url: Option<String>,
+}
+
+/// Which contact details to include when formatting a `Holder`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ContactStyle {
+    /// Include the e-mail address and URL, if any
+    #[default]
+    Full,
+    /// Include the e-mail address, if any, but not the URL
+    EmailOnly,
+    /// Only the name
+    NameOnly,
+}
+
+fn is_url(text: &str) -> bool {
+    text.starts_with("http://") || text.starts_with("https://")
+}
+
+/// If the text ends with a bracketed contact detail, split it off, returning the rest and the contact.
+fn split_trailing_contact(text: &str) -> Option<(&str, &str, char)> {
+    let text = text.trim_end();
+    let close = text.chars().last()?;
+    let open = match close {
+        '>' => '<',
+        ')' => '(',
+        _ => return None,
+    };
+    let open_pos = text.rfind(open)?;
+    let contact = text[open_pos + 1..text.len() - 1].trim();
+    Some((&text[..open_pos], contact, open))
+}
+
+impl Holder {
+    /// Create a holder with just a name.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.trim().to_string(),
+            email: None,
+            url: None,
+        }
+    }
+
+    /// Parse a holder, splitting off any trailing `<e-mail>`, `<URL>`, or `(URL)`.
+    ///
+    /// Parenthesized text that is not a URL, as in "Foo (Bar)", stays part of the name.
+    pub fn parse(text: &str) -> Self {
+        let mut holder = Holder::new(text);
+        let mut rest = text.trim();
+        while let Some((before, contact, open)) = split_trailing_contact(rest) {
+            let before = before.trim_end().trim_end_matches(',').trim_end();
+            if before.is_empty() {
+                break;
+            }
+            let contact = contact.strip_prefix("mailto:").unwrap_or(contact);
+            if is_url(contact) && holder.url.is_none() {
+                holder.url = Some(contact.to_string());
+            } else if open == '<' && contact.contains('@') && holder.email.is_none() {
+                holder.email = Some(contact.to_string());
+            } else {
+                break;
+            }
+            rest = before;
+        }
+        holder.name = rest.to_string();
+        holder
+    }
+
+    /// Do these refer to the same holder?
+    ///
+    /// The names must match, ignoring case and runs of whitespace, and so must the e-mail addresses
+    /// and URLs, ignoring case. So this is transitive: `Jane Doe` is not the same holder as
+    /// `Jane Doe <jane@example.org>`. When grouping the lines of statements, a name alone is
+    /// also taken to be the same as the only form of that name with contact details, if there is
+    /// exactly one: see `Copyright::normalize`.
+    pub fn is_same_holder(&self, other: &Holder) -> bool {
+        self.key() == other.key()
+    }
+
+    /// The name key (see `name_key`) and contact details folded to lower case, for grouping holders.
+    pub(crate) fn key(&self) -> HolderKey {
+        (
+            self.name_key(),
+            self.email.as_deref().map(str::to_lowercase),
+            self.url.as_deref().map(str::to_lowercase),
+        )
+    }
+
+    pub(crate) fn has_contact(&self) -> bool {
+        self.email.is_some() || self.url.is_some()
+    }
+
+    /// The name, folded to lower case with whitespace collapsed, for comparing holders.
+    pub fn name_key(&self) -> String {
+        self.name
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Fill in any contact details we lack from another form of the same holder,
+    /// so that we end up with the most complete form.
+    pub fn merge(&mut self, other: &Holder) {
+        if self.email.is_none() {
+            self.email.clone_from(&other.email);
+        }
+        if self.url.is_none() {
+            self.url.clone_from(&other.url);
+        }
+    }
+
+    /// Format, choosing which contact details to include.
+    pub fn to_string_with(&self, style: ContactStyle) -> String {
+        let mut s = self.name.clone();
+        if style != ContactStyle::NameOnly {
+            if let Some(email) = &self.email {
+                s.push_str(&format!(" <{email}>"));
+            }
+        }
+        if style == ContactStyle::Full {
+            if let Some(url) = &self.url {
+                s.push_str(&format!(" ({url})"));
+            }
+        }
+        s
+    }
+}
+
+impl Display for Holder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_string_with(ContactStyle::Full))
+    }
+}
+
+impl From<&str> for Holder {
+    fn from(text: &str) -> Self {
+        Holder::parse(text)
+    }
+}
+
+pub(crate) type HolderKey = (String, Option<String>, Option<String>);
+
+/// Which holders among a set of holders are the same.
+///
+/// Holders are the same if `Holder::is_same_holder` says so, or if one is a name alone and the
+/// other is the only form of that name with contact details. This does not depend on the order
+/// of the holders, and is transitive.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct HolderIdentities {
+    /// For each name key, the key of its only form with contact details, or `None` if there are several
+    contact_forms: HashMap<String, Option<HolderKey>>,
+}
+
+impl HolderIdentities {
+    pub(crate) fn new<'a>(holders: impl IntoIterator<Item = &'a Holder>) -> Self {
+        let mut contact_forms: HashMap<String, Option<HolderKey>> = HashMap::new();
+        for holder in holders.into_iter().filter(|holder| holder.has_contact()) {
+            let key = holder.key();
+            contact_forms
+                .entry(key.0.clone())
+                .and_modify(|only| {
+                    if only.as_ref() != Some(&key) {
+                        *only = None;
+                    }
+                })
+                .or_insert(Some(key));
+        }
+        Self { contact_forms }
+    }
+
+    /// The key shared by all holders that are the same as this one
+    pub(crate) fn resolve(&self, holder: &Holder) -> HolderKey {
+        let key = holder.key();
+        if holder.has_contact() {
+            return key;
+        }
+        match self.contact_forms.get(&key.0) {
+            Some(Some(only)) => only.clone(),
+            _ => key,
+        }
+    }
+}
+
+/// Corporate suffixes that may follow a comma while still being part of the preceding name,
+/// as in "Collabora, Ltd." or "Foo, Inc.".
+///
+/// Compared case-insensitively, ignoring a trailing period.
+pub(crate) const CORPORATE_SUFFIXES: &[&str] = &[
+    "ab",
+    "ag",
+    "as",
+    "b.v",
+    "bv",
+    "co",
+    "corp",
+    "corporation",
+    "e.v",
+    "gmbh",
+    "inc",
+    "incorporated",
+    "kg",
+    "l.l.c",
+    "limited",
+    "llc",
+    "llp",
+    "lp",
+    "ltd",
+    "n.v",
+    "nv",
+    "oy",
+    "plc",
+    "pty ltd",
+    "pty. ltd",
+    "s.a",
+    "s.l",
+    "s.r.l",
+    "sa",
+    "srl",
+];
+
+/// Words that may follow "and" or a comma, but are not a holder by themselves, as in "Jane Doe and others".
+const NOT_HOLDERS: &[&str] = &[
+    "all contributors",
+    "contributors",
+    "et al",
+    "other contributors",
+    "others",
+];
+
+fn normalize_for_lookup(piece: &str) -> String {
+    piece.trim().trim_end_matches('.').to_lowercase()
+}
+
+fn is_corporate_suffix(piece: &str) -> bool {
+    CORPORATE_SUFFIXES.contains(&normalize_for_lookup(piece).as_str())
+}
+
+fn is_not_holder(piece: &str) -> bool {
+    NOT_HOLDERS.contains(&normalize_for_lookup(piece).as_str())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Separator {
+    Comma,
+    Conjunction,
+}
+
+/// If `rest` starts with whitespace, then the (case-insensitive) word, then more whitespace,
+/// return the length of all that.
+fn spaced_word_len(rest: &str, word: &str) -> Option<usize> {
+    let after_space = rest.trim_start();
+    if after_space.len() == rest.len() {
+        return None;
+    }
+    let candidate = after_space.get(..word.len())?;
+    if !candidate.eq_ignore_ascii_case(word) {
+        return None;
+    }
+    let after_word = &after_space[word.len()..];
+    let after_second_space = after_word.trim_start();
+    if after_second_space.len() == after_word.len() {
+        return None;
+    }
+    Some(rest.len() - after_second_space.len())
+}
+
+/// Find the separators between holders: their start and end byte offsets and kind.
+fn find_separators(holder: &str) -> Vec<(usize, usize, Separator)> {
+    let mut separators = vec![];
+    let mut pos = 0;
+    while pos < holder.len() {
+        let rest = &holder[pos..];
+        if rest.starts_with(',') {
+            separators.push((pos, pos + 1, Separator::Comma));
+            pos += 1;
+            continue;
+        }
+        if let Some(len) = spaced_word_len(rest, "and").or_else(|| spaced_word_len(rest, "&")) {
+            separators.push((pos, pos + len, Separator::Conjunction));
+            pos += len;
+            continue;
+        }
+        pos += rest.chars().next().map_or(1, char::len_utf8);
+    }
+    separators
+}
+
+/// A piece of a holder string between separators: its byte range and the separator before it.
+type Piece = (usize, usize, Option<Separator>);
+
+/// Does the piece at the start of `pieces`, which follows a comma, look like another holder
+/// rather than the rest of a name like "Doe, Jane"?
+///
+/// It does if its name has several words, is followed by a corporate suffix ("Bar, LLC"),
+/// or is part of a list ending in a conjunction ("Alice, Bob, and Carol").
+fn looks_like_another_holder(holder: &str, pieces: &[Piece]) -> bool {
+    let (start, end, _) = pieces[0];
+    if Holder::parse(&holder[start..end])
+        .name
+        .split_whitespace()
+        .count()
+        > 1
+    {
+        return true;
+    }
+    let mut later = pieces[1..]
+        .iter()
+        .filter(|(start, end, _)| !holder[*start..*end].trim().is_empty());
+    if later.clone().next().is_some_and(|&(start, end, before)| {
+        before == Some(Separator::Comma) && is_corporate_suffix(&holder[start..end])
+    }) {
+        return true;
+    }
+    later.any(|(_, _, before)| *before == Some(Separator::Conjunction))
+}
+
+/// Split a holder string naming several holders into the individual holders.
+///
+/// Splits on "and", "&", and commas that are followed by another holder (see
+/// `looks_like_another_holder`), keeping corporate suffixes ("Collabora, Ltd."),
+/// names written surname first ("Doe, Jane"), and phrases like "and others"
+/// attached to the preceding name. A name that only repeats itself ("Johnson and Johnson")
+/// is taken to be a single holder.
+pub fn split_holder_names(holder: &str) -> Vec<String> {
+    // Byte ranges of the text between separators
+    let mut raw_pieces: Vec<Piece> = vec![];
+    let mut start = 0;
+    let mut previous_separator = None;
+    for (sep_start, sep_end, kind) in find_separators(holder) {
+        raw_pieces.push((start, sep_start, previous_separator));
+        start = sep_end;
+        previous_separator = Some(kind);
+    }
+    raw_pieces.push((start, holder.len(), previous_separator));
+
+    // Byte ranges of each holder in the original string
+    let mut pieces: Vec<(usize, usize)> = vec![];
+    for (index, &(start, end, before)) in raw_pieces.iter().enumerate() {
+        let piece = &holder[start..end];
+        if piece.trim().is_empty() {
+            // Such as between the comma and "and" of "Alice, Bob, and Carol"
+            continue;
+        }
+        let joins_previous = !pieces.is_empty()
+            && ((before == Some(Separator::Comma)
+                && (is_corporate_suffix(piece)
+                    || !looks_like_another_holder(holder, &raw_pieces[index..])))
+                || is_not_holder(piece));
+        match pieces.last_mut() {
+            Some(last) if joins_previous => last.1 = end,
+            _ => pieces.push((start, end)),
+        }
+    }
+
+    let names: Vec<String> = pieces
+        .into_iter()
+        .map(|(start, end)| holder[start..end].trim().to_string())
+        .filter(|piece| !piece.is_empty())
+        .collect();
+    if names.len() > 1
+        && names
+            .iter()
+            .map(|name| Holder::parse(name).name_key())
+            .all_equal()
+    {
+        return vec![holder.trim().to_string()];
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split_holder_names, ContactStyle, Holder, HolderIdentities};
+
+    #[test]
+    fn parse_holder() {
+        let jane = Holder::parse("Jane Doe <jane@example.org>");
+        assert_eq!(jane.name, "Jane Doe");
+        assert_eq!(jane.email.as_deref(), Some("jane@example.org"));
+        assert_eq!(jane.url, None);
+        assert_eq!(jane.to_string(), "Jane Doe <jane@example.org>");
+
+        let foo = Holder::parse("Foo, Inc. (https://example.com)");
+        assert_eq!(foo.name, "Foo, Inc.");
+        assert_eq!(foo.url.as_deref(), Some("https://example.com"));
+
+        let both = Holder::parse("Jane Doe <mailto:jane@example.org> <https://jane.example.org>");
+        assert_eq!(both.name, "Jane Doe");
+        assert_eq!(both.email.as_deref(), Some("jane@example.org"));
+        assert_eq!(both.url.as_deref(), Some("https://jane.example.org"));
+        assert_eq!(
+            both.to_string(),
+            "Jane Doe <jane@example.org> (https://jane.example.org)"
+        );
+        assert_eq!(
+            both.to_string_with(ContactStyle::EmailOnly),
+            "Jane Doe <jane@example.org>"
+        );
+        assert_eq!(both.to_string_with(ContactStyle::NameOnly), "Jane Doe");
+
+        // Not contact details
+        assert_eq!(Holder::parse("Foo (Bar)").name, "Foo (Bar)");
+        assert_eq!(
+            Holder::parse("<jane@example.org>").name,
+            "<jane@example.org>"
+        );
+    }
+
+    #[test]
+    fn same_holder() {
+        let mut jane = Holder::parse("Jane Doe");
+        let jane_email = Holder::parse("Jane  doe <jane@example.org>");
+        let jane_other_email = Holder::parse("Jane Doe <jane@example.com>");
+        assert!(jane.is_same_holder(&Holder::parse("jane  DOE")));
+        assert!(jane_email.is_same_holder(&Holder::parse("Jane Doe <JANE@example.org>")));
+        assert!(!jane.is_same_holder(&jane_email));
+        assert!(!jane_email.is_same_holder(&jane_other_email));
+        assert!(!jane.is_same_holder(&Holder::parse("John Doe")));
+
+        // A name alone goes with the only form of that name with contact details
+        let identities = HolderIdentities::new([&jane, &jane_email]);
+        assert_eq!(identities.resolve(&jane), identities.resolve(&jane_email));
+        // but not if there is more than one
+        let identities = HolderIdentities::new([&jane, &jane_email, &jane_other_email]);
+        assert_ne!(identities.resolve(&jane), identities.resolve(&jane_email));
+        assert_ne!(
+            identities.resolve(&jane),
+            identities.resolve(&jane_other_email)
+        );
+
+        jane.merge(&jane_email);
+        assert_eq!(jane.name, "Jane Doe");
+        assert_eq!(jane.email.as_deref(), Some("jane@example.org"));
+        // Does not overwrite what we already have
+        jane.merge(&jane_other_email);
+        assert_eq!(jane.email.as_deref(), Some("jane@example.org"));
+    }
+
+    #[test]
+    fn split() {
+        assert_eq!(split_holder_names("Alice and Bob"), vec!["Alice", "Bob"]);
+        assert_eq!(split_holder_names("Alice & Bob"), vec!["Alice", "Bob"]);
+        assert_eq!(
+            split_holder_names("Foo Inc., Bar GmbH"),
+            vec!["Foo Inc.", "Bar GmbH"]
+        );
+        assert_eq!(
+            split_holder_names("Alice, Bob, and Carol"),
+            vec!["Alice", "Bob", "Carol"]
+        );
+        assert_eq!(split_holder_names("Rylie Pavlik"), vec!["Rylie Pavlik"]);
+    }
+
+    #[test]
+    fn corporate_suffixes() {
+        assert_eq!(
+            split_holder_names("Collabora, Ltd."),
+            vec!["Collabora, Ltd."]
+        );
+        assert_eq!(
+            split_holder_names("Foo, Inc. and Collabora, Ltd."),
+            vec!["Foo, Inc.", "Collabora, Ltd."]
+        );
+        assert_eq!(
+            split_holder_names("Foo, Inc., Bar, LLC"),
+            vec!["Foo, Inc.", "Bar, LLC"]
+        );
+        assert_eq!(split_holder_names("Foo Co., Ltd."), vec!["Foo Co., Ltd."]);
+    }
+
+    #[test]
+    fn not_split() {
+        assert_eq!(
+            split_holder_names("Jane Doe and others"),
+            vec!["Jane Doe and others"]
+        );
+        assert_eq!(split_holder_names("AT&T"), vec!["AT&T"]);
+        assert_eq!(
+            split_holder_names("Alexandra Anderson"),
+            vec!["Alexandra Anderson"]
+        );
+        // Surname first
+        assert_eq!(split_holder_names("Doe, Jane"), vec!["Doe, Jane"]);
+        assert_eq!(
+            split_holder_names("Doe, Jane <jane@example.org>"),
+            vec!["Doe, Jane <jane@example.org>"]
+        );
+        // A name repeating itself
+        assert_eq!(
+            split_holder_names("Johnson & Johnson"),
+            vec!["Johnson & Johnson"]
+        );
+    }
+}
@@ -2,14 +2,37 @@
 //
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+mod aliases;
+mod clusters;
 mod copyright;
 mod copyright_parsing;
+mod diagnostics;
+mod diff;
+mod format;
+mod holder;
 pub mod raw_year;
+#[cfg(feature = "serde")]
+pub mod serde_string;
+mod validation;
 mod years;
 
-pub use copyright::{Copyright, CopyrightDecompositionError, DecomposedCopyright};
+pub use aliases::{AliasResolution, HolderAlias, HolderAliases, MailmapError};
+pub use clusters::{cluster_holders, fold_holder_name, HolderCluster, HolderClusterOptions};
+pub use copyright::{Copyright, DecomposedCopyright};
+pub use diagnostics::{
+    CopyrightDecompositionError, CopyrightErrorReason, InvalidYearRangeError, YearParseError,
+};
+pub use diff::{CopyrightChange, CopyrightDiff};
+pub use format::{
+    CopyrightFormatter, CopyrightPrefix, HolderOrder, UnknownFormatOptionError, YearCompaction,
+};
+pub use holder::{split_holder_names, ContactStyle, Holder};
 pub use raw_year::{
     options::YearRangeNormalization,
     traits::{SingleYearNormalizationOptions, YearRangeNormalizationOptions},
 };
-pub use years::{coalesce_years, Year, YearRange, YearRangeCollection, YearSpec};
+pub use validation::{ImplausibleYearError, YearPlausibility, DEFAULT_EARLIEST_PLAUSIBLE_YEAR};
+pub use years::{
+    coalesce_years, CoalescingPolicy, OpenRangeForm, OpenRangeStyle, OpenYearRange, TracedYearSpec,
+    Year, YearProvenance, YearRange, YearRangeCollection, YearSpec,
+};
@@ -12,5 +12,7 @@ mod util;
 pub(crate) const CENTURY_DURATION: u16 = 100;
 
 pub(crate) use traits::{RawYear, RawYearRange};
+pub(crate) use types::{FourDigitYear, YearExpr};
+pub(crate) use util::current_year;
 
 pub use options::YearRangeNormalization;
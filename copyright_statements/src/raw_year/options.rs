@@ -2,12 +2,20 @@
 //
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use super::traits::{
-    SetSingleYearNormalizationOptions, SetYearRangeNormalizationOptions,
-    SingleYearNormalizationOptions, YearRangeNormalizationOptions,
+use super::{
+    traits::{
+        SetSingleYearNormalizationOptions, SetYearRangeNormalizationOptions,
+        SingleYearNormalizationOptions, YearRangeNormalizationOptions,
+    },
+    util::{current_year, DEFAULT_FOUR_DIGIT_YEAR_BOUNDS, DEFAULT_TWO_DIGIT_YEAR_WINDOW_START},
 };
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct YearRangeNormalization {
     /// Should allow the century to be guessed entirely when there is no four-digit year
     /// suitably close to imply a century?
@@ -19,12 +27,94 @@ pub struct YearRangeNormalization {
     /// Should we allow the century part of a year range's endpoint to be inferred
     /// across a century boundary based on the other endpoint's known century.
     pub allow_mixed_size_implied_century_rollover: bool,
+    /// Should "2019/2020" be a range including any years in between,
+    /// rather than a list of just those two years?
+    pub allow_slash_ranges: bool,
+    /// Must a two-digit year standing alone be written with an apostrophe ('98)
+    /// to be recognized as a year?
+    pub require_apostrophe_for_two_digit_years: bool,
+    /// The year that open-ended ranges like "2018-present" extend to,
+    /// and that `two_digit_year_pivot` is relative to: the current year if `None`.
+    pub reference_year: Option<u16>,
+    /// Two-digit years are taken to be at most this many years after the reference year,
+    /// and otherwise in the past: a sliding window.
+    /// If `None`, 00-59 are 2000-2059 and 60-99 are 1960-1999, whatever the reference year.
+    pub two_digit_year_pivot: Option<u16>,
+    /// The earliest four-digit year to recognize: 1900 if `None`.
+    pub earliest_year: Option<u16>,
+    /// The latest four-digit year to recognize: 2099 if `None`.
+    pub latest_year: Option<u16>,
+}
+
+impl Default for YearRangeNormalization {
+    /// The most conservative options, recognizing four-digit years from 1900 to 2099.
+    ///
+    /// There is no `reference_year`, so open-ended ranges like "2018-present" extend to the current
+    /// year according to the system clock, and a `two_digit_year_pivot` is relative to it too.
+    /// Parsing the same text may therefore give different results from one year to the next,
+    /// including when deserializing with `serde_string`. Set `reference_year` for repeatable results.
+    fn default() -> Self {
+        Self {
+            allow_century_guess: false,
+            allow_assuming_y2k_span: false,
+            allow_mixed_size_implied_century_rollover: false,
+            allow_slash_ranges: false,
+            require_apostrophe_for_two_digit_years: false,
+            reference_year: None,
+            two_digit_year_pivot: None,
+            earliest_year: None,
+            latest_year: None,
+        }
+    }
+}
+
+impl YearRangeNormalization {
+    /// Copy the settings of any other options type
+    pub(crate) fn from_options(options: impl YearRangeNormalizationOptions) -> Self {
+        let (earliest, latest) = options.get_four_digit_year_bounds();
+        let mut converted = Self {
+            allow_century_guess: options.get_allow_century_guess(),
+            allow_assuming_y2k_span: options.get_allow_assuming_y2k_span(),
+            allow_mixed_size_implied_century_rollover: options
+                .get_allow_mixed_size_implied_century_rollover(),
+            allow_slash_ranges: options.get_allow_slash_ranges(),
+            require_apostrophe_for_two_digit_years: options
+                .get_require_apostrophe_for_two_digit_years(),
+            reference_year: Some(options.get_reference_year()),
+            two_digit_year_pivot: None,
+            earliest_year: Some(earliest),
+            latest_year: Some(latest),
+        };
+        let window_start = options.get_two_digit_year_window_start();
+        if window_start != DEFAULT_TWO_DIGIT_YEAR_WINDOW_START {
+            // Express the window relative to the reference year
+            let reference_year = converted.get_reference_year();
+            converted.two_digit_year_pivot =
+                Some((window_start + 99).saturating_sub(reference_year));
+        }
+        converted
+    }
 }
 
 impl SingleYearNormalizationOptions for YearRangeNormalization {
     fn get_allow_century_guess(&self) -> bool {
         self.allow_century_guess
     }
+
+    fn get_two_digit_year_window_start(&self) -> u16 {
+        match self.two_digit_year_pivot {
+            Some(pivot) => (self.get_reference_year() + pivot).saturating_sub(99),
+            None => DEFAULT_TWO_DIGIT_YEAR_WINDOW_START,
+        }
+    }
+
+    fn get_four_digit_year_bounds(&self) -> (u16, u16) {
+        let (earliest, latest) = DEFAULT_FOUR_DIGIT_YEAR_BOUNDS;
+        (
+            self.earliest_year.unwrap_or(earliest),
+            self.latest_year.unwrap_or(latest),
+        )
+    }
 }
 
 impl SetSingleYearNormalizationOptions for YearRangeNormalization {
@@ -44,6 +134,18 @@ impl YearRangeNormalizationOptions for YearRangeNormalization {
     fn get_allow_mixed_size_implied_century_rollover(&self) -> bool {
         self.allow_mixed_size_implied_century_rollover
     }
+
+    fn get_allow_slash_ranges(&self) -> bool {
+        self.allow_slash_ranges
+    }
+
+    fn get_require_apostrophe_for_two_digit_years(&self) -> bool {
+        self.require_apostrophe_for_two_digit_years
+    }
+
+    fn get_reference_year(&self) -> u16 {
+        self.reference_year.unwrap_or_else(current_year)
+    }
 }
 
 impl SetYearRangeNormalizationOptions for YearRangeNormalization {
@@ -60,4 +162,25 @@ impl SetYearRangeNormalizationOptions for YearRangeNormalization {
             ..self
         }
     }
+
+    fn allow_slash_ranges(self, allow: bool) -> Self {
+        Self {
+            allow_slash_ranges: allow,
+            ..self
+        }
+    }
+
+    fn require_apostrophe_for_two_digit_years(self, require: bool) -> Self {
+        Self {
+            require_apostrophe_for_two_digit_years: require,
+            ..self
+        }
+    }
+
+    fn reference_year(self, year: u16) -> Self {
+        Self {
+            reference_year: Some(year),
+            ..self
+        }
+    }
 }
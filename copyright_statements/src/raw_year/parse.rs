@@ -4,11 +4,11 @@
 
 use nom::{
     branch::alt,
-    bytes::complete::tag,
-    character::complete::{one_of, space0},
-    combinator::{map, map_res, recognize},
+    bytes::complete::{tag, tag_no_case},
+    character::complete::{one_of, satisfy, space0, space1},
+    combinator::{map, map_res, not, opt, recognize, verify},
     multi::count,
-    sequence::{pair, preceded, separated_pair, tuple},
+    sequence::{pair, preceded, separated_pair, terminated, tuple},
     IResult,
 };
 
@@ -16,25 +16,35 @@ use super::{
     types::{FourDigitYear, TwoDigitYear, YearExpr},
     RawYear,
 };
+use crate::years::OpenRangeForm;
 
 fn digit(input: &str) -> IResult<&str, char> {
     one_of("0123456789")(input)
 }
 
-fn century(input: &str) -> IResult<&str, &str> {
-    alt((tag("19"), tag("20")))(input)
+/// Any four digits not starting with zero.
+///
+/// Which of these are plausible years is decided by the options, when converting.
+fn four_digit_year(input: &str) -> IResult<&str, FourDigitYear> {
+    map_res(
+        recognize(pair(one_of("123456789"), count(digit, 3))),
+        |out: &str| out.parse::<u16>().map(FourDigitYear::new),
+    )(input)
 }
 
-fn four_digit_year(input: &str) -> IResult<&str, FourDigitYear> {
-    map_res(recognize(pair(century, count(digit, 2))), |out: &str| {
-        out.parse::<u16>().map(FourDigitYear::new)
-    })(input)
+/// Characters that may mark an abbreviated year: '98
+pub(crate) const APOSTROPHES: [char; 3] = ['\'', '\u{2019}', '\u{2018}'];
+
+fn apostrophe(input: &str) -> IResult<&str, char> {
+    one_of(&APOSTROPHES[..])(input)
 }
 
+/// A two-digit year, possibly written with an apostrophe: 98 or '98
 fn two_digit_year(input: &str) -> IResult<&str, TwoDigitYear> {
-    map_res(recognize(count(digit, 2)), |out: &str| {
-        out.parse::<u16>().map(TwoDigitYear::new)
-    })(input)
+    map_res(
+        preceded(opt(apostrophe), recognize(count(digit, 2))),
+        |out: &str| out.parse::<u16>().map(TwoDigitYear::new),
+    )(input)
 }
 
 fn year(input: &str) -> IResult<&str, YearExpr> {
@@ -44,18 +54,89 @@ fn year(input: &str) -> IResult<&str, YearExpr> {
     ))(input)
 }
 
+/// Two digits within the given bounds, inclusive
+fn bounded_two_digits<'a>(min: u8, max: u8) -> impl FnMut(&'a str) -> IResult<&'a str, u8> {
+    verify(
+        map_res(recognize(count(digit, 2)), |out: &str| out.parse::<u8>()),
+        move |n| (min..=max).contains(n),
+    )
+}
+
+/// A full ISO 8601 date, of which we only keep the year: 2021-03-04
+pub(crate) fn date(input: &str) -> IResult<&str, FourDigitYear> {
+    terminated(
+        four_digit_year,
+        tuple((
+            tag("-"),
+            bounded_two_digits(1, 12),
+            tag("-"),
+            bounded_two_digits(1, 31),
+            not(digit),
+        )),
+    )(input)
+}
+
+/// A hyphen, en dash, or em dash
+fn dash(input: &str) -> IResult<&str, &str> {
+    alt((tag("-"), tag("\u{2013}"), tag("\u{2014}")))(input)
+}
+
 fn range_delim(input: &str) -> IResult<&str, &str> {
-    recognize(tuple((space0, tag("-"), space0)))(input)
+    recognize(tuple((space0, dash, space0)))(input)
+}
+
+/// Words meaning "up to now" at the end of an open range
+fn present_word(input: &str) -> IResult<&str, &str> {
+    terminated(
+        alt((
+            tag_no_case("present"),
+            tag_no_case("now"),
+            tag_no_case("today"),
+        )),
+        not(satisfy(char::is_alphanumeric)),
+    )(input)
+}
+
+/// An open-ended year range: "2018-", "2018-present", "2018–now", or "since 2018".
+///
+/// Returns the beginning year and how the open end was written.
+pub(crate) fn open_year_range(input: &str) -> IResult<&str, (YearExpr, OpenRangeForm)> {
+    alt((
+        map(
+            separated_pair(
+                terminated(tag_no_case("since"), not(satisfy(char::is_alphanumeric))),
+                space1,
+                year,
+            ),
+            |(word, begin)| (begin, OpenRangeForm::Prefix(word.to_string())),
+        ),
+        map(
+            pair(
+                year,
+                terminated(
+                    recognize(tuple((space0, dash, opt(preceded(space0, present_word))))),
+                    // Must not actually be a closed range
+                    not(preceded(space0, digit)),
+                ),
+            ),
+            |(begin, suffix)| (begin, OpenRangeForm::Suffix(suffix.to_string())),
+        ),
+    ))(input)
 }
 
 fn convert_range<T: RawYear, U: RawYear>(range: (T, U)) -> (YearExpr, YearExpr) {
     (range.0.to_year_expr(), range.1.to_year_expr())
 }
 
-pub(crate) fn year_range_44(input: &str) -> IResult<&str, (FourDigitYear, FourDigitYear)> {
+/// Two four-digit years separated like a range, even if the end is before the beginning
+fn four_digit_year_pair(input: &str) -> IResult<&str, (FourDigitYear, FourDigitYear)> {
     separated_pair(four_digit_year, range_delim, four_digit_year)(input)
 }
 
+pub(crate) fn year_range_44(input: &str) -> IResult<&str, (FourDigitYear, FourDigitYear)> {
+    verify(four_digit_year_pair, |(b, e)| b <= e)(input)
+}
+
 pub(crate) fn year_range_42(input: &str) -> IResult<&str, (FourDigitYear, TwoDigitYear)> {
     separated_pair(four_digit_year, range_delim, two_digit_year)(input)
 }
@@ -68,6 +149,11 @@ pub(crate) fn year_range_22(input: &str) -> IResult<&str, (TwoDigitYear, TwoDigi
     separated_pair(two_digit_year, range_delim, two_digit_year)(input)
 }
 
+/// Two years separated by a slash, "2019/2020", which may or may not mean a range
+pub(crate) fn slash_year_pair(input: &str) -> IResult<&str, (YearExpr, YearExpr)> {
+    separated_pair(year, tag("/"), year)(input)
+}
+
 pub(crate) fn year_range(input: &str) -> IResult<&str, (YearExpr, YearExpr)> {
     alt((
         map(year_range_44, convert_range),
@@ -82,7 +168,11 @@ pub(crate) fn year_spec(input: &str) -> IResult<&str, (YearExpr, YearExpr)> {
     preceded(
         space0,
         alt((
-            // could be a year range: always try this first
+            // could be a date, which looks like the start of a range, so try this first
+            map(date, |y| (y.to_year_expr(), y.to_year_expr())),
+            // could be a year range, or something that looks like one but ends before it begins:
+            // keep those whole so they can be reported rather than misread
+            map(four_digit_year_pair, convert_range),
             year_range,
             // Failing that, could be a single year (represented by a range with same begin and end)
             map(year, |y| (y, y)),
@@ -95,11 +185,16 @@ mod tests {
     use nom::{combinator::all_consuming, Finish};
 
     use crate::raw_year::{
+        options::YearRangeNormalization,
+        traits::ConfigurableRawYear,
         types::{FourDigitYear, YearExpr},
-        RawYear,
     };
 
-    use super::{four_digit_year, two_digit_year, year, year_range, year_range_44};
+    use super::{
+        date, four_digit_year, open_year_range, slash_year_pair, two_digit_year, year, year_range,
+        year_range_44, year_spec,
+    };
+    use crate::years::OpenRangeForm;
 
     #[test]
     fn parse_year() {
@@ -119,7 +214,8 @@ mod tests {
                 .finish()
                 .unwrap()
                 .1
-                .to_four_digit(),
+                .try_to_four_digit(YearRangeNormalization::default())
+                .unwrap(),
             FourDigitYear::new(2020)
         );
 
@@ -128,7 +224,8 @@ mod tests {
                 .finish()
                 .unwrap()
                 .1
-                .to_four_digit(),
+                .try_to_four_digit(YearRangeNormalization::default())
+                .unwrap(),
             FourDigitYear::new(2022)
         );
         assert_eq!(
@@ -136,7 +233,8 @@ mod tests {
                 .finish()
                 .unwrap()
                 .1
-                .to_four_digit(),
+                .try_to_four_digit(YearRangeNormalization::default())
+                .unwrap(),
             FourDigitYear::new(2019)
         );
         assert_eq!(
@@ -144,7 +242,8 @@ mod tests {
                 .finish()
                 .unwrap()
                 .1
-                .to_four_digit(),
+                .try_to_four_digit(YearRangeNormalization::default())
+                .unwrap(),
             FourDigitYear::new(2022)
         );
         assert_eq!(
@@ -152,7 +251,8 @@ mod tests {
                 .finish()
                 .unwrap()
                 .1
-                .to_four_digit(),
+                .try_to_four_digit(YearRangeNormalization::default())
+                .unwrap(),
             FourDigitYear::new(1995)
         );
         assert!(all_consuming(year)("20222").finish().is_err());
@@ -181,6 +281,23 @@ mod tests {
         assert!(all_consuming(four_digit_year)("20222").finish().is_err());
     }
 
+    #[test]
+    fn parse_four_digit_year_outside_default_bounds() {
+        // Whether these are plausible years is up to the options, when converting
+        assert_eq!(
+            four_digit_year("1821").finish().unwrap(),
+            ("", FourDigitYear::new(1821))
+        );
+        assert_eq!(
+            all_consuming(year_range)("1850-2150").finish().unwrap().1,
+            (
+                YearExpr::new_four_digit(1850),
+                YearExpr::new_four_digit(2150)
+            )
+        );
+        assert!(all_consuming(four_digit_year)("0995").finish().is_err());
+    }
+
     #[test]
     fn parse_two_digityear() {
         assert!(all_consuming(two_digit_year)("202").finish().is_err());
@@ -189,15 +306,30 @@ mod tests {
         assert!(all_consuming(two_digit_year)("1995").finish().is_err());
 
         assert_eq!(
-            two_digit_year("20").finish().unwrap().1.to_four_digit(),
+            two_digit_year("20")
+                .finish()
+                .unwrap()
+                .1
+                .try_to_four_digit(YearRangeNormalization::default())
+                .unwrap(),
             FourDigitYear::new(2020)
         );
         assert_eq!(
-            two_digit_year("19").finish().unwrap().1.to_four_digit(),
+            two_digit_year("19")
+                .finish()
+                .unwrap()
+                .1
+                .try_to_four_digit(YearRangeNormalization::default())
+                .unwrap(),
             FourDigitYear::new(2019)
         );
         assert_eq!(
-            two_digit_year("85").finish().unwrap().1.to_four_digit(),
+            two_digit_year("85")
+                .finish()
+                .unwrap()
+                .1
+                .try_to_four_digit(YearRangeNormalization::default())
+                .unwrap(),
             FourDigitYear::new(1985)
         );
     }
@@ -215,7 +347,8 @@ mod tests {
                 .finish()
                 .unwrap()
                 .1
-                .to_four_digit(),
+                .try_to_four_digit(YearRangeNormalization::default())
+                .unwrap(),
             FourDigitYear::new(2020)
         );
         assert_eq!(
@@ -223,7 +356,8 @@ mod tests {
                 .finish()
                 .unwrap()
                 .1
-                .to_four_digit(),
+                .try_to_four_digit(YearRangeNormalization::default())
+                .unwrap(),
             FourDigitYear::new(2022)
         );
         assert_eq!(
@@ -231,7 +365,8 @@ mod tests {
                 .finish()
                 .unwrap()
                 .1
-                .to_four_digit(),
+                .try_to_four_digit(YearRangeNormalization::default())
+                .unwrap(),
             FourDigitYear::new(2019)
         );
         assert_eq!(
@@ -239,7 +374,8 @@ mod tests {
                 .finish()
                 .unwrap()
                 .1
-                .to_four_digit(),
+                .try_to_four_digit(YearRangeNormalization::default())
+                .unwrap(),
             FourDigitYear::new(2022)
         );
         assert_eq!(
@@ -247,7 +383,8 @@ mod tests {
                 .finish()
                 .unwrap()
                 .1
-                .to_four_digit(),
+                .try_to_four_digit(YearRangeNormalization::default())
+                .unwrap(),
             FourDigitYear::new(1995)
         );
         assert!(all_consuming(year)("20222").finish().is_err());
@@ -297,4 +434,148 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn parse_open_year_range() {
+        let suffix = |s: &str| OpenRangeForm::Suffix(s.to_string());
+        assert_eq!(
+            all_consuming(open_year_range)("2018-").finish().unwrap().1,
+            (YearExpr::new_four_digit(2018), suffix("-"))
+        );
+        assert_eq!(
+            all_consuming(open_year_range)("2018-present")
+                .finish()
+                .unwrap()
+                .1,
+            (YearExpr::new_four_digit(2018), suffix("-present"))
+        );
+        assert_eq!(
+            all_consuming(open_year_range)("2018 - Present")
+                .finish()
+                .unwrap()
+                .1,
+            (YearExpr::new_four_digit(2018), suffix(" - Present"))
+        );
+        assert_eq!(
+            all_consuming(open_year_range)("2018\u{2013}now")
+                .finish()
+                .unwrap()
+                .1,
+            (YearExpr::new_four_digit(2018), suffix("\u{2013}now"))
+        );
+        assert_eq!(
+            all_consuming(open_year_range)("since 2018")
+                .finish()
+                .unwrap()
+                .1,
+            (
+                YearExpr::new_four_digit(2018),
+                OpenRangeForm::Prefix("since".to_string())
+            )
+        );
+        assert_eq!(
+            open_year_range("2018-, Jane Doe").finish().unwrap(),
+            (", Jane Doe", (YearExpr::new_four_digit(2018), suffix("-")))
+        );
+
+        // These are closed ranges or not ranges at all
+        assert!(open_year_range("2018-2020").is_err());
+        assert!(open_year_range("2018 - 20").is_err());
+        assert!(open_year_range("2018").is_err());
+        assert!(all_consuming(open_year_range)("2018-nowhere").is_err());
+        assert!(open_year_range("sinceX 2018").is_err());
+    }
+
+    #[test]
+    fn parse_dashes() {
+        let expected = (
+            YearExpr::new_four_digit(2019),
+            YearExpr::new_four_digit(2021),
+        );
+        assert_eq!(
+            all_consuming(year_range)("2019\u{2013}2021")
+                .finish()
+                .unwrap()
+                .1,
+            expected
+        );
+        assert_eq!(
+            all_consuming(year_range)("2019 \u{2014} 2021")
+                .finish()
+                .unwrap()
+                .1,
+            expected
+        );
+        assert_eq!(
+            all_consuming(year_range)("2019\u{2013}21")
+                .finish()
+                .unwrap()
+                .1,
+            (YearExpr::new_four_digit(2019), YearExpr::new_two_digit(21))
+        );
+    }
+
+    #[test]
+    fn parse_apostrophe_years() {
+        assert_eq!(
+            all_consuming(year)("'98").finish().unwrap().1,
+            YearExpr::new_two_digit(98)
+        );
+        assert_eq!(
+            all_consuming(two_digit_year)("\u{2019}04")
+                .finish()
+                .unwrap()
+                .1
+                .try_to_four_digit(YearRangeNormalization::default())
+                .unwrap(),
+            FourDigitYear::new(2004)
+        );
+        assert_eq!(
+            all_consuming(year_range)("'98-'02").finish().unwrap().1,
+            (YearExpr::new_two_digit(98), YearExpr::new_two_digit(2))
+        );
+        assert!(all_consuming(year)("'1998").finish().is_err());
+        assert!(all_consuming(year)("'9").finish().is_err());
+    }
+
+    #[test]
+    fn parse_slash_pair() {
+        assert_eq!(
+            all_consuming(slash_year_pair)("2019/2020")
+                .finish()
+                .unwrap()
+                .1,
+            (
+                YearExpr::new_four_digit(2019),
+                YearExpr::new_four_digit(2020)
+            )
+        );
+        assert!(all_consuming(slash_year_pair)("2019/").finish().is_err());
+    }
+
+    #[test]
+    fn parse_date() {
+        assert_eq!(
+            all_consuming(date)("2021-03-04").finish().unwrap().1,
+            FourDigitYear::new(2021)
+        );
+        assert!(all_consuming(date)("2021-13-04").finish().is_err());
+        assert!(all_consuming(date)("2021-03-32").finish().is_err());
+        assert!(all_consuming(date)("2021-03").finish().is_err());
+        // a date is a single year, not a range
+        assert_eq!(
+            all_consuming(year_spec)("2021-03-04").finish().unwrap().1,
+            (
+                YearExpr::new_four_digit(2021),
+                YearExpr::new_four_digit(2021)
+            )
+        );
+        assert_eq!(
+            all_consuming(year_spec)("2021-2023").finish().unwrap().1,
+            (
+                YearExpr::new_four_digit(2021),
+                YearExpr::new_four_digit(2023)
+            )
+        );
+    }
 }
@@ -2,7 +2,10 @@
 //
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use super::types::{FourDigitYear, TwoDigitYear, YearExpr};
+use super::{
+    types::{FourDigitYear, TwoDigitYear, YearExpr},
+    util::{current_year, DEFAULT_FOUR_DIGIT_YEAR_BOUNDS, DEFAULT_TWO_DIGIT_YEAR_WINDOW_START},
+};
 
 pub(crate) trait IsProper {
     /// Is this a proper range, with the beginning year less than or equal to the end year?
@@ -30,6 +33,20 @@ pub trait SingleYearNormalizationOptions {
     /// suitably close to imply a century, and, if this is used on a range, the two-digit begin
     /// is less than or equal to the two-digit end so we cannot infer that they span Y2K
     fn get_allow_century_guess(&self) -> bool;
+
+    /// Get the first year of the 100-year window that two-digit years are placed in.
+    ///
+    /// Defaults to 1960, so that 00-59 are 2000-2059 and 60-99 are 1960-1999.
+    fn get_two_digit_year_window_start(&self) -> u16 {
+        DEFAULT_TWO_DIGIT_YEAR_WINDOW_START
+    }
+
+    /// Get the earliest and latest four-digit years to recognize, inclusive.
+    ///
+    /// Defaults to 1900 through 2099.
+    fn get_four_digit_year_bounds(&self) -> (u16, u16) {
+        DEFAULT_FOUR_DIGIT_YEAR_BOUNDS
+    }
 }
 pub trait SetSingleYearNormalizationOptions: SingleYearNormalizationOptions {
     /// Set whether we allow the century to be guessed entirely when there is no four-digit year
@@ -47,6 +64,21 @@ pub trait YearRangeNormalizationOptions: SingleYearNormalizationOptions {
     /// Get whether we allow the century part of a year range's endpoint to be inferred
     /// across a century boundary based on the other endpoint's known century.
     fn get_allow_mixed_size_implied_century_rollover(&self) -> bool;
+
+    /// Get whether "2019/2020" should be a range including any years in between,
+    /// rather than a list of just those two years.
+    fn get_allow_slash_ranges(&self) -> bool;
+
+    /// Get whether a two-digit year standing alone must be written with an apostrophe ('98)
+    /// to be recognized as a year.
+    fn get_require_apostrophe_for_two_digit_years(&self) -> bool;
+
+    /// Get the year that open-ended ranges like "2018-present" extend to.
+    ///
+    /// Defaults to the current year.
+    fn get_reference_year(&self) -> u16 {
+        current_year()
+    }
 }
 
 pub(crate) trait SetYearRangeNormalizationOptions:
@@ -60,6 +92,17 @@ pub(crate) trait SetYearRangeNormalizationOptions:
     /// Set whether we allow the century part of a year range's endpoint to be inferred
     /// across a century boundary based on the other endpoint's known century.
     fn allow_mixed_size_implied_century_rollover(self, allow: bool) -> Self;
+
+    /// Set whether "2019/2020" should be a range including any years in between,
+    /// rather than a list of just those two years.
+    fn allow_slash_ranges(self, allow: bool) -> Self;
+
+    /// Set whether a two-digit year standing alone must be written with an apostrophe ('98)
+    /// to be recognized as a year.
+    fn require_apostrophe_for_two_digit_years(self, require: bool) -> Self;
+
+    /// Set the year that open-ended ranges like "2018-present" extend to.
+    fn reference_year(self, year: u16) -> Self;
 }
 
 pub(crate) trait RawYear {
@@ -75,10 +118,6 @@ pub(crate) trait RawYear {
     #[must_use]
     fn try_as_four_digit(&self) -> Option<FourDigitYear>;
 
-    /// Using a simple heuristic if needed, get the year as a four-digit year.
-    #[must_use]
-    fn to_four_digit(&self) -> FourDigitYear;
-
     /// If this is a two digit year, use the provided century to make a 4 digit year
     #[must_use]
     fn to_four_digit_with_century_hint(&self, century: u16) -> FourDigitYear;
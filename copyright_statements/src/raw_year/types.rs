@@ -3,7 +3,10 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use super::{
-    traits::{ConfigurableRawYearRange, IsProper, IsSingleYear, YearRangeNormalizationOptions},
+    traits::{
+        ConfigurableRawYear, ConfigurableRawYearRange, IsProper, IsSingleYear,
+        SingleYearNormalizationOptions, YearRangeNormalizationOptions,
+    },
     util, RawYear, RawYearRange,
 };
 
@@ -49,10 +52,6 @@ impl RawYear for TwoDigitYear {
         None
     }
 
-    fn to_four_digit(&self) -> FourDigitYear {
-        FourDigitYear(util::guess_four_digit_from_two_digit(self.0))
-    }
-
     fn to_four_digit_with_century_hint(&self, century: u16) -> FourDigitYear {
         FourDigitYear(util::compose_year(century, self.0))
     }
@@ -65,6 +64,18 @@ impl RawYear for TwoDigitYear {
     }
 }
 
+impl ConfigurableRawYear for TwoDigitYear {
+    fn try_to_four_digit(
+        &self,
+        options: impl SingleYearNormalizationOptions,
+    ) -> Option<FourDigitYear> {
+        Some(FourDigitYear(util::four_digit_in_window(
+            self.0,
+            options.get_two_digit_year_window_start(),
+        )))
+    }
+}
+
 impl From<TwoDigitYear> for YearExpr {
     fn from(value: TwoDigitYear) -> Self {
         value.to_year_expr()
@@ -101,10 +112,6 @@ impl RawYear for FourDigitYear {
         Some(*self)
     }
 
-    fn to_four_digit(&self) -> FourDigitYear {
-        *self
-    }
-
     fn to_four_digit_with_century_hint(&self, _century: u16) -> FourDigitYear {
         *self
     }
@@ -117,6 +124,18 @@ impl RawYear for FourDigitYear {
     }
 }
 
+impl ConfigurableRawYear for YearExpr {
+    fn try_to_four_digit(
+        &self,
+        options: impl SingleYearNormalizationOptions,
+    ) -> Option<FourDigitYear> {
+        match self {
+            YearExpr::TwoDigit(y) => y.try_to_four_digit(options),
+            YearExpr::FourDigit(y) => Some(*y),
+        }
+    }
+}
+
 impl From<FourDigitYear> for YearExpr {
     fn from(value: FourDigitYear) -> Self {
         value.to_year_expr()
@@ -145,13 +164,6 @@ impl RawYear for YearExpr {
         }
     }
 
-    fn to_four_digit(&self) -> FourDigitYear {
-        match self {
-            YearExpr::TwoDigit(y) => y.to_four_digit(),
-            YearExpr::FourDigit(y) => y.to_four_digit(),
-        }
-    }
-
     fn to_four_digit_with_century_hint(&self, century: u16) -> FourDigitYear {
         match self {
             YearExpr::TwoDigit(y) => y.to_four_digit_with_century_hint(century),
@@ -234,7 +246,10 @@ impl RawYearRange for (TwoDigitYear, TwoDigitYear) {
             )
         } else {
             // guess the first year's century, re-use it for the second year
-            let b = b.to_four_digit();
+            let b = FourDigitYear(util::four_digit_in_window(
+                b.0,
+                util::DEFAULT_TWO_DIGIT_YEAR_WINDOW_START,
+            ));
             let e = e.to_four_digit_with_century_hint(b.century());
             (b, e)
         }
@@ -251,16 +266,18 @@ impl ConfigurableRawYearRange for (TwoDigitYear, TwoDigitYear) {
         if b <= e {
             if options.get_allow_century_guess() {
                 // guess the first year's century, re-use it for the second year
-                let b = b.to_four_digit();
+                let b = b.try_to_four_digit(options)?;
                 let e = e.to_four_digit_with_century_hint(b.century());
                 return Some((b, e));
             }
         } else {
-            // range spans y2k?
+            // range spans y2k? (or rather, the turn of the century within our two-digit year window)
             if options.get_allow_assuming_y2k_span() {
+                let window_end = options.get_two_digit_year_window_start() + 99;
+                let century = util::get_century(window_end);
                 return Some((
-                    b.to_four_digit_with_century_hint(20),
-                    e.to_four_digit_with_century_hint(21),
+                    b.to_four_digit_with_century_hint(century - 1),
+                    e.to_four_digit_with_century_hint(century),
                 ));
             }
         }
@@ -429,23 +446,28 @@ mod tests {
 
     use crate::raw_year::{
         options::YearRangeNormalization,
-        traits::{ConfigurableRawYearRange, IsProper, TryIsProper},
-        types::{FourDigitYear, TwoDigitYear},
+        traits::{ConfigurableRawYear, ConfigurableRawYearRange, IsProper, TryIsProper},
+        types::{FourDigitYear, TwoDigitYear, YearExpr},
     };
 
     use super::{RawYear, RawYearRange};
 
     #[test]
     fn to_four_digit_year() {
-        assert_eq!(TwoDigitYear(59).to_four_digit().into_inner(), 2059);
+        let to_four_digit = |y: YearExpr| {
+            y.try_to_four_digit(YearRangeNormalization::default())
+                .unwrap()
+                .into_inner()
+        };
+        assert_eq!(to_four_digit(TwoDigitYear(59).into()), 2059);
 
-        assert_eq!(FourDigitYear(2059).to_four_digit().into_inner(), 2059);
-        assert_eq!(FourDigitYear(1959).to_four_digit().into_inner(), 1959);
+        assert_eq!(to_four_digit(FourDigitYear(2059).into()), 2059);
+        assert_eq!(to_four_digit(FourDigitYear(1959).into()), 1959);
 
-        assert_eq!(TwoDigitYear(95).to_four_digit().into_inner(), 1995);
+        assert_eq!(to_four_digit(TwoDigitYear(95).into()), 1995);
 
-        assert_eq!(FourDigitYear(1995).to_four_digit().into_inner(), 1995);
-        assert_eq!(FourDigitYear(2095).to_four_digit().into_inner(), 2095);
+        assert_eq!(to_four_digit(FourDigitYear(1995).into()), 1995);
+        assert_eq!(to_four_digit(FourDigitYear(2095).into()), 2095);
     }
 
     #[test]
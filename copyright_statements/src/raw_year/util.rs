@@ -6,24 +6,31 @@
 /// Important note: Humans use "century" to refer to a 1-indexed number of hundred-year periods since
 /// the beginning of the era (year 0). That means technically they're one larger than the year
 /// integer-divided by 100. This is a mess and super annoying.
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use super::CENTURY_DURATION;
 
 // Ugh. Centuries
 
-pub(crate) fn guess_century(two_digit_year: u16) -> u8 {
-    if two_digit_year < 60 {
-        21
-    } else {
-        20
-    }
-}
+/// The default first year of the 100-year window two-digit years are placed in:
+/// 00-59 are 2000-2059 and 60-99 are 1960-1999.
+pub(crate) const DEFAULT_TWO_DIGIT_YEAR_WINDOW_START: u16 = 1960;
+
+/// The default earliest and latest four-digit years we recognize.
+pub(crate) const DEFAULT_FOUR_DIGIT_YEAR_BOUNDS: (u16, u16) = (1900, 2099);
 
 pub(crate) fn compose_year(century: u16, two_digit: u16) -> u16 {
     (century - 1) * CENTURY_DURATION + two_digit
 }
 
-pub(crate) fn guess_four_digit_from_two_digit(two_digit: u16) -> u16 {
-    compose_year(u16::from(guess_century(two_digit)), two_digit)
+/// Place a two-digit year in the 100-year window starting at `window_start`.
+pub(crate) fn four_digit_in_window(two_digit: u16, window_start: u16) -> u16 {
+    let year = compose_year(get_century(window_start), two_digit);
+    if year < window_start {
+        year + CENTURY_DURATION
+    } else {
+        year
+    }
 }
 
 pub(crate) fn get_century(year: u16) -> u16 {
@@ -34,12 +41,38 @@ pub(crate) fn get_two_digit_year(year: u16) -> u16 {
     year % CENTURY_DURATION
 }
 
+/// Get the (proleptic Gregorian) year containing the given number of days since 1970-01-01.
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+pub(crate) fn year_from_unix_days(days: u64) -> u16 {
+    const DAYS_PER_ERA: i64 = 146097;
+    let z = days as i64 + 719468;
+    let era = z.div_euclid(DAYS_PER_ERA);
+    let day_of_era = z.rem_euclid(DAYS_PER_ERA);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    // Years here start in March, so January and February belong to the next one
+    let march_based_month = (5 * day_of_year + 2) / 153;
+    let year = year_of_era + era * 400 + i64::from(march_based_month >= 10);
+    year as u16
+}
+
+/// Get the current year (UTC), according to the system clock.
+pub(crate) fn current_year() -> u16 {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() / 86400)
+        .unwrap_or_default();
+    year_from_unix_days(days)
+}
+
 #[cfg(test)]
 mod tests {
 
     use crate::raw_year::util::get_two_digit_year;
 
-    use super::get_century;
+    use super::{four_digit_in_window, get_century, year_from_unix_days};
 
     #[test]
     fn test_get_century() {
@@ -56,4 +89,28 @@ mod tests {
         assert_eq!(get_two_digit_year(1995), 95);
         assert_eq!(get_two_digit_year(2095), 95);
     }
+
+    #[test]
+    fn test_four_digit_in_window() {
+        assert_eq!(four_digit_in_window(59, 1960), 2059);
+        assert_eq!(four_digit_in_window(60, 1960), 1960);
+        assert_eq!(four_digit_in_window(99, 1960), 1999);
+        assert_eq!(four_digit_in_window(0, 1960), 2000);
+        // A 1950s-era codebase
+        assert_eq!(four_digit_in_window(50, 1940), 1950);
+        assert_eq!(four_digit_in_window(39, 1940), 2039);
+        // Far in the future
+        assert_eq!(four_digit_in_window(5, 2030), 2105);
+        assert_eq!(four_digit_in_window(30, 2030), 2030);
+    }
+
+    #[test]
+    fn test_year_from_unix_days() {
+        assert_eq!(year_from_unix_days(0), 1970);
+        assert_eq!(year_from_unix_days(10956), 1999);
+        assert_eq!(year_from_unix_days(10957), 2000);
+        assert_eq!(year_from_unix_days(11016), 2000); // 2000-02-29
+        assert_eq!(year_from_unix_days(20088), 2024);
+        assert_eq!(year_from_unix_days(20089), 2025);
+    }
 }
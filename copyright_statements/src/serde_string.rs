@@ -0,0 +1,236 @@
+// Copyright 2025, Collabora, Ltd.
+//
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Serialize as a string through `Display`, and deserialize by parsing with the default options.
+//!
+//! Without this, the types in this crate serialize to a structured representation.
+//! Use this on a field instead with `#[serde(with = "copyright_statements::serde_string")]`,
+//! for any type implementing `StringForm`: `Copyright`, `DecomposedCopyright`, `YearSpec`,
+//! `YearRange`, and `Year`.
+//!
+//! Open-ended ranges are resolved again when parsed, so they will extend to the current year.
+
+use std::fmt::Display;
+
+use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+use crate::{
+    Copyright, CopyrightDecompositionError, DecomposedCopyright, Year, YearParseError, YearRange,
+    YearSpec,
+};
+
+/// A type with a string representation that parses back to an equal value.
+///
+/// This is `Display` and `FromStr`, except that in a `Copyright`, lines without years keep the
+/// "Copyright" word, so they are not read back as free text.
+pub trait StringForm: Sized {
+    type Err: Display;
+
+    fn to_string_form(&self) -> String;
+
+    fn from_string_form(text: &str) -> Result<Self, Self::Err>;
+}
+
+macro_rules! string_form_from_display {
+    ($($type:ty => $err:ty),+) => {
+        $(impl StringForm for $type {
+            type Err = $err;
+
+            fn to_string_form(&self) -> String {
+                self.to_string()
+            }
+
+            fn from_string_form(text: &str) -> Result<Self, Self::Err> {
+                text.parse()
+            }
+        })+
+    };
+}
+
+string_form_from_display!(
+    Year => YearParseError,
+    YearRange => YearParseError,
+    YearSpec => YearParseError,
+    DecomposedCopyright => CopyrightDecompositionError
+);
+
+impl StringForm for Copyright {
+    type Err = CopyrightDecompositionError;
+
+    fn to_string_form(&self) -> String {
+        match self {
+            Copyright::Complex(text) => text.clone(),
+            _ => self
+                .decomposed_lines()
+                .iter()
+                .map(|line| {
+                    if line.years.is_empty() {
+                        format!("Copyright {line}")
+                    } else {
+                        line.to_string()
+                    }
+                })
+                .chain(self.residue_lines().into_iter().map(String::from))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+
+    fn from_string_form(text: &str) -> Result<Self, Self::Err> {
+        text.parse()
+    }
+}
+
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: StringForm,
+    S: Serializer,
+{
+    serializer.serialize_str(&value.to_string_form())
+}
+
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: StringForm,
+    D: Deserializer<'de>,
+{
+    let text = String::deserialize(deserializer)?;
+    T::from_string_form(&text).map_err(D::Error::custom)
+}
+
+#[cfg(test)]
+mod test {
+    use std::fmt::Debug;
+
+    use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+    use super::StringForm;
+
+    use crate::{
+        Copyright, DecomposedCopyright, Year, YearRange, YearRangeNormalization, YearSpec,
+    };
+
+    #[derive(Serialize, Deserialize)]
+    struct AsString<T: StringForm>(#[serde(with = "crate::serde_string")] T);
+
+    /// Check that both representations round-trip, and agree with each other.
+    fn check_consistent<T>(value: T) -> (String, String)
+    where
+        T: Serialize + DeserializeOwned + StringForm + PartialEq + Debug + Clone,
+    {
+        let structured = serde_json::to_string(&value).unwrap();
+        let from_structured: T = serde_json::from_str(&structured).unwrap();
+        assert_eq!(from_structured, value);
+
+        let string = serde_json::to_string(&AsString(value.clone())).unwrap();
+        let AsString(from_string): AsString<T> = serde_json::from_str(&string).unwrap();
+        assert_eq!(from_string, value);
+
+        assert_eq!(
+            string,
+            serde_json::to_string(&from_structured.to_string_form()).unwrap()
+        );
+        (structured, string)
+    }
+
+    /// Check round-tripping a value with open-ended ranges.
+    ///
+    /// Those are resolved again against the current year when parsed, rather than the reference year
+    /// of the value, so only the way they are written survives the string representation.
+    fn check_open_consistent<T>(value: T)
+    where
+        T: Serialize + DeserializeOwned + StringForm + PartialEq + Debug + Clone,
+    {
+        let structured = serde_json::to_string(&value).unwrap();
+        let from_structured: T = serde_json::from_str(&structured).unwrap();
+        assert_eq!(from_structured, value);
+
+        let string = serde_json::to_string(&AsString(value.clone())).unwrap();
+        let AsString(from_string): AsString<T> = serde_json::from_str(&string).unwrap();
+        assert_eq!(from_string.to_string_form(), value.to_string_form());
+    }
+
+    #[test]
+    fn copyright() {
+        for statement in [
+            "Copyright (c) 2019, 2021-2023, Jane Doe <jane@example.com>",
+            "Copyright 2015, Jane Doe\nAll rights reserved.",
+            "Copyright The Chromium Authors",
+            "Public domain",
+        ] {
+            let copyright: Copyright = statement.parse().unwrap();
+            check_consistent(copyright.clone());
+            for line in copyright.decomposed_lines() {
+                check_consistent(line.clone());
+            }
+        }
+
+        let options = YearRangeNormalization {
+            reference_year: Some(2025),
+            ..Default::default()
+        };
+        let open = Copyright::try_parse(
+            options,
+            "Copyright 2015, Jane Doe\nCopyright 2018-present, Example Corp. (https://example.com)",
+        )
+        .unwrap();
+        check_open_consistent(open.clone());
+        for line in open.decomposed_lines() {
+            check_open_consistent(line.clone());
+        }
+
+        let (structured, string) = check_consistent(DecomposedCopyright::new(
+            &[YearSpec::single(2019)],
+            "Jane Doe",
+        ));
+        assert_eq!(
+            structured,
+            r#"{"years":[{"single_year":2019}],"holder":{"name":"Jane Doe","email":null,"url":null}}"#
+        );
+        assert_eq!(string, r#""2019, Jane Doe""#);
+    }
+
+    #[test]
+    fn years() {
+        let (structured, string) = check_consistent(Year(2019));
+        assert_eq!(
+            (structured.as_str(), string.as_str()),
+            ("2019", r#""2019""#)
+        );
+
+        let (structured, string) = check_consistent(YearRange::new(Year(2019), Year(2021)));
+        assert_eq!(structured, r#"{"begin":2019,"end":2021}"#);
+        assert_eq!(string, r#""2019-2021""#);
+        assert!(serde_json::from_str::<YearRange>(r#"{"begin":2021,"end":2019}"#).is_err());
+
+        for spec in ["2019", "2019-2021"] {
+            check_consistent(spec.parse::<YearSpec>().unwrap());
+        }
+        let options = YearRangeNormalization {
+            reference_year: Some(2025),
+            ..Default::default()
+        };
+        for spec in ["2019-present", "since 2019"] {
+            check_open_consistent(YearSpec::parse(options, spec).unwrap());
+        }
+        assert!("2021-2019".parse::<YearSpec>().is_err());
+    }
+
+    #[test]
+    fn normalization() {
+        let options = YearRangeNormalization {
+            allow_century_guess: true,
+            earliest_year: Some(1970),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&options).unwrap();
+        let parsed: YearRangeNormalization = serde_json::from_str(&json).unwrap();
+        assert_eq!(format!("{parsed:?}"), format!("{options:?}"));
+
+        // Missing fields take their defaults
+        let parsed: YearRangeNormalization =
+            serde_json::from_str(r#"{"allow_slash_ranges":true}"#).unwrap();
+        assert!(parsed.allow_slash_ranges && !parsed.allow_century_guess);
+    }
+}
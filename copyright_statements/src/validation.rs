@@ -0,0 +1,177 @@
+// Copyright 2025, Collabora, Ltd.
+//
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checking that the years in parsed copyright statements are plausible.
+
+use crate::{
+    raw_year::current_year, years::YearRange, Copyright, YearRangeNormalization, YearSpec,
+};
+
+/// The earliest plausible year if none is given: few copyrightable works in a source tree are older.
+pub const DEFAULT_EARLIEST_PLAUSIBLE_YEAR: u16 = 1970;
+
+/// Bounds on the years that are plausible in copyright statements.
+///
+/// Parsing accepts any year within `YearRangeNormalization` bounds, so typos like "2092" for "2022"
+/// get through, while typos outside them like "2202" leave the statement undecomposed.
+/// Years outside these narrower bounds are worth a second look.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct YearPlausibility {
+    /// Years after this are in the future: the current year if `None`.
+    pub reference_year: Option<u16>,
+    /// Years before this are implausible, such as the year the project started:
+    /// `DEFAULT_EARLIEST_PLAUSIBLE_YEAR` if `None`.
+    pub earliest_year: Option<u16>,
+}
+
+/// A year spec outside the bounds of a `YearPlausibility`.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ImplausibleYearError {
+    /// A single year, or the end of a range, after the reference year.
+    ///
+    /// Open-ended ranges are only checked for where they begin.
+    #[error("{spec} goes past {latest}, into the future")]
+    Future { spec: YearSpec, latest: u16 },
+    /// A single year, or the beginning of a range, before the earliest plausible year
+    #[error("{spec} starts before {earliest}")]
+    TooEarly { spec: YearSpec, earliest: u16 },
+}
+
+impl YearPlausibility {
+    /// The latest plausible year
+    pub fn latest(&self) -> u16 {
+        self.reference_year.unwrap_or_else(current_year)
+    }
+
+    /// The earliest plausible year
+    pub fn earliest(&self) -> u16 {
+        self.earliest_year
+            .unwrap_or(DEFAULT_EARLIEST_PLAUSIBLE_YEAR)
+    }
+
+    /// Check a single year spec.
+    pub fn check_spec(&self, spec: &YearSpec) -> Result<(), ImplausibleYearError> {
+        let range = YearRange::from(spec.clone());
+        // The end of an open range was not written, so it cannot be a typo
+        let end = match spec {
+            YearSpec::OpenRange(open) => open.begin(),
+            _ => range.end(),
+        };
+        if end.0 > self.latest() {
+            return Err(ImplausibleYearError::Future {
+                spec: spec.clone(),
+                latest: self.latest(),
+            });
+        }
+        if range.begin().0 < self.earliest() {
+            return Err(ImplausibleYearError::TooEarly {
+                spec: spec.clone(),
+                earliest: self.earliest(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Parse a statement and check it, returning any implausible year specs.
+    ///
+    /// Any four-digit year is accepted while parsing, so that years outside the bounds of `options`
+    /// are reported too, rather than just leaving the statement undecomposed.
+    pub fn check_statement(
+        &self,
+        options: YearRangeNormalization,
+        statement: &str,
+    ) -> Vec<ImplausibleYearError> {
+        let options = YearRangeNormalization {
+            earliest_year: Some(1000),
+            latest_year: Some(9999),
+            ..options
+        };
+        Copyright::try_parse(options, statement)
+            .map(|copyright| self.check(&copyright))
+            .unwrap_or_default()
+    }
+
+    /// Check every year spec of the decomposed lines of a statement, returning any implausible ones.
+    pub fn check(&self, copyright: &Copyright) -> Vec<ImplausibleYearError> {
+        copyright
+            .decomposed_lines()
+            .iter()
+            .flat_map(|line| &line.years)
+            .filter_map(|spec| self.check_spec(spec).err())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Copyright, ImplausibleYearError, YearPlausibility, YearRangeNormalization};
+
+    #[test]
+    fn plausibility() {
+        let options = YearRangeNormalization {
+            reference_year: Some(2025),
+            ..Default::default()
+        };
+        let plausibility = YearPlausibility {
+            reference_year: Some(2025),
+            earliest_year: Some(2010),
+        };
+        let check = |statement: &str| {
+            plausibility
+                .check(&Copyright::try_parse(options, statement).unwrap())
+                .iter()
+                .map(ImplausibleYearError::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        assert!(check("Copyright 2010, 2019-2025, Jane Doe").is_empty());
+        assert!(check("Copyright 2019-present, Jane Doe").is_empty());
+        assert_eq!(
+            check("Copyright 2099, Jane Doe\nCopyright 2019-2030, Bob"),
+            vec![
+                "2099 goes past 2025, into the future",
+                "2019-2030 goes past 2025, into the future"
+            ]
+        );
+        assert_eq!(
+            check("Copyright 1901, 2005-2012, Jane Doe"),
+            vec!["1901 starts before 2010", "2005-2012 starts before 2010"]
+        );
+        assert!(check("All rights reserved.").is_empty());
+
+        // Years the parser would not accept are still checked
+        let check_statement = |statement: &str| {
+            plausibility
+                .check_statement(options, statement)
+                .iter()
+                .map(ImplausibleYearError::to_string)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            check_statement("Copyright 2202, Jane Doe"),
+            vec!["2202 goes past 2025, into the future"]
+        );
+        assert_eq!(
+            check_statement("Copyright 2019-2202, Jane Doe\nCopyright 1021, Bob"),
+            vec![
+                "2019-2202 goes past 2025, into the future",
+                "1021 starts before 2010"
+            ]
+        );
+        assert!(check_statement("Copyright 2019-2025, Jane Doe").is_empty());
+
+        // The default is more lenient about old years
+        let default = YearPlausibility {
+            reference_year: Some(2025),
+            ..Default::default()
+        };
+        let copyright = Copyright::try_parse(options, "Copyright 1998, 2005, Jane Doe").unwrap();
+        assert!(default.check(&copyright).is_empty());
+    }
+}
@@ -2,11 +2,26 @@
 //
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use std::{collections::BinaryHeap, fmt::Display};
+#[cfg(feature = "serde")]
+use std::convert::TryFrom;
+use std::{collections::BinaryHeap, fmt::Display, str::FromStr};
 
 use itertools::Itertools;
 
+use crate::{
+    copyright_parsing,
+    diagnostics::{self, InvalidYearRangeError, YearParseError},
+    format::UnknownFormatOptionError,
+    raw_year::traits::YearRangeNormalizationOptions,
+    YearRangeNormalization,
+};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct Year(pub u16);
 
 impl Display for Year {
@@ -24,17 +39,65 @@ pub trait YearContainment {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "UncheckedYearRange")
+)]
 pub struct YearRange {
     begin: Year,
     end: Year,
 }
 
+/// The fields of a `YearRange`, before checking that it does not end before it begins.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UncheckedYearRange {
+    begin: Year,
+    end: Year,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedYearRange> for YearRange {
+    type Error = InvalidYearRangeError;
+
+    fn try_from(value: UncheckedYearRange) -> Result<Self, Self::Error> {
+        Self::try_new(value.begin, value.end)
+    }
+}
+
 impl YearRange {
     pub(crate) fn new(begin: Year, end: Year) -> Self {
         assert!(begin <= end);
         Self { begin, end }
     }
 
+    /// Create a range, checking that it does not end before it begins.
+    pub fn try_new(begin: Year, end: Year) -> Result<Self, InvalidYearRangeError> {
+        if begin <= end {
+            Ok(Self { begin, end })
+        } else {
+            Err(InvalidYearRangeError { begin, end })
+        }
+    }
+
+    /// Parse a single year or closed range, such as "1995-2003" or "'98", with the given options.
+    ///
+    /// An open-ended range is an error, since it is not a fixed range of years.
+    pub fn parse(
+        options: impl YearRangeNormalizationOptions + Copy,
+        text: &str,
+    ) -> Result<Self, YearParseError> {
+        match YearSpec::parse(options, text)? {
+            YearSpec::SingleYear(year) => Ok(year.into()),
+            YearSpec::ClosedRange(range) => Ok(range),
+            YearSpec::OpenRange(_) => Err(YearParseError::unexpected_form(
+                text,
+                "a single year or closed range",
+            )),
+        }
+    }
+
     pub fn begin(&self) -> Year {
         self.begin
     }
@@ -50,8 +113,8 @@ impl YearRange {
     fn can_add(&self, new_year: &Year) -> bool {
         // within the range
         self.contains_year(new_year)
-            || (*new_year == Year(self.end.0 + 1))// appends one year to the end
-            || (*new_year == Year(self.begin.0 - 1)) // appends one year to the beginning
+            || self.end.0.checked_add(1) == Some(new_year.0) // appends one year to the end
+            || self.begin.0.checked_sub(1) == Some(new_year.0) // appends one year to the beginning
     }
 
     fn can_merge(&self, new_range: &YearRange) -> bool {
@@ -62,6 +125,37 @@ impl YearRange {
         Self::new(self.begin.min(other.begin), self.end.max(other.end))
     }
 
+    /// The single range covering both, if they overlap or are adjacent.
+    pub fn union(&self, other: &YearRange) -> Option<YearRange> {
+        if self.can_merge(other) || other.can_merge(self) {
+            Some(self.merge_with(*other))
+        } else {
+            None
+        }
+    }
+
+    /// The years included in both, if any.
+    pub fn intersection(&self, other: &YearRange) -> Option<YearRange> {
+        let begin = self.begin.max(other.begin);
+        let end = self.end.min(other.end);
+        (begin <= end).then(|| Self::new(begin, end))
+    }
+
+    /// The years in this range but not in the other: zero, one or two ranges.
+    pub fn subtract(&self, other: &YearRange) -> Vec<YearRange> {
+        if self.intersection(other).is_none() {
+            return vec![*self];
+        }
+        let mut remaining = Vec::with_capacity(2);
+        if self.begin < other.begin {
+            remaining.push(Self::new(self.begin, Year(other.begin.0 - 1)));
+        }
+        if self.end > other.end {
+            remaining.push(Self::new(Year(other.end.0 + 1), self.end));
+        }
+        remaining
+    }
+
     fn partial_order_single_year(&self, single: &Year) -> Option<std::cmp::Ordering> {
         if self.is_single_year() {
             self.begin.partial_cmp(single)
@@ -83,12 +177,12 @@ impl YearRange {
     fn try_add(&self, new_year: Year) -> Option<Self> {
         if new_year <= self.end && new_year >= self.begin {
             Some(*self)
-        } else if new_year == Year(self.end.0 + 1) {
+        } else if self.end.0.checked_add(1) == Some(new_year.0) {
             Some(Self {
                 begin: self.begin,
                 end: new_year,
             })
-        } else if new_year == Year(self.begin.0 - 1) {
+        } else if self.begin.0.checked_sub(1) == Some(new_year.0) {
             Some(Self {
                 begin: new_year,
                 end: self.end,
@@ -110,10 +204,52 @@ impl From<YearSpec> for YearRange {
         match ys {
             YearSpec::SingleYear(y) => y.into(),
             YearSpec::ClosedRange(range) => range,
+            YearSpec::OpenRange(open) => open.resolved(),
+        }
+    }
+}
+
+impl From<YearRange> for YearSpec {
+    fn from(range: YearRange) -> Self {
+        if range.is_single_year() {
+            YearSpec::SingleYear(range.begin)
+        } else {
+            YearSpec::ClosedRange(range)
+        }
+    }
+}
+
+impl FromStr for Year {
+    type Err = YearParseError;
+
+    /// Parse a single year, with the default options.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse()? {
+            YearSpec::SingleYear(year) => Ok(year),
+            _ => Err(YearParseError::unexpected_form(s, "a single year")),
         }
     }
 }
 
+impl FromStr for YearRange {
+    type Err = YearParseError;
+
+    /// Parse a single year or closed range, with the default options: see `YearRange::parse`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        YearRange::parse(YearRangeNormalization::default(), s)
+    }
+}
+
+impl FromStr for YearSpec {
+    type Err = YearParseError;
+
+    /// Parse a single year, closed range, or open-ended range, with the default options:
+    /// see `YearSpec::parse`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        YearSpec::parse(YearRangeNormalization::default(), s)
+    }
+}
+
 impl Display for YearRange {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.begin == self.end {
@@ -124,11 +260,65 @@ impl Display for YearRange {
     }
 }
 
+/// How far apart years may be and still be merged into a single range when coalescing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum CoalescingPolicy {
+    /// Only merge years that overlap or are adjacent: "2019, 2021" stays a list
+    #[default]
+    Strict,
+    /// Also bridge gaps of up to this many missing years: with 1, "2019, 2021" becomes "2019-2021"
+    MaxGap(u16),
+    /// Merge all the years into one range from the first to the last
+    Span,
+}
+
+impl CoalescingPolicy {
+    fn can_merge(self, a: &YearRange, b: &YearRange) -> bool {
+        match self {
+            CoalescingPolicy::Strict => a.can_merge(b),
+            CoalescingPolicy::MaxGap(max_gap) => {
+                let later_begin = i32::from(a.begin.0.max(b.begin.0));
+                let earlier_end = i32::from(a.end.0.min(b.end.0));
+                // Negative if they overlap
+                let missing_years = later_begin - earlier_end - 1;
+                missing_years <= i32::from(max_gap)
+            }
+            CoalescingPolicy::Span => true,
+        }
+    }
+}
+
+impl FromStr for CoalescingPolicy {
+    type Err = UnknownFormatOptionError;
+
+    /// Parse "strict", "span", or the maximum number of missing years to bridge.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "strict" => Ok(CoalescingPolicy::Strict),
+            "span" => Ok(CoalescingPolicy::Span),
+            _ => s.parse().map(CoalescingPolicy::MaxGap).map_err(|_| {
+                UnknownFormatOptionError::new(
+                    "year coalescing policy",
+                    s,
+                    "strict, span, or a number of years",
+                )
+            }),
+        }
+    }
+}
+
+/// Merge consecutive ranges in sorted order, as far as the policy allows.
 pub fn coalesce_years(
     years: impl IntoIterator<Item = YearRange>,
+    policy: CoalescingPolicy,
 ) -> impl Iterator<Item = YearRange> {
-    years.into_iter().coalesce(|a, b| {
-        if a.can_merge(&b) {
+    years.into_iter().coalesce(move |a, b| {
+        if policy.can_merge(&a, &b) {
             Ok(a.merge_with(b))
         } else {
             Err((a, b))
@@ -136,14 +326,164 @@ pub fn coalesce_years(
     })
 }
 
+/// How the open end of an open-ended year range was written.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum OpenRangeForm {
+    /// The text following the beginning year: "-" or "-present" or "–now"
+    Suffix(String),
+    /// The word preceding the beginning year: "since"
+    Prefix(String),
+}
+
+/// An open-ended year range (2018-, 2018-present, since 2018),
+/// with the end resolved to a reference year (normally the current year).
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "UncheckedOpenYearRange")
+)]
+pub struct OpenYearRange {
+    begin: Year,
+    resolved_end: Year,
+    form: OpenRangeForm,
+}
+
+/// The fields of an `OpenYearRange`, before making sure the resolved end is not before the beginning.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UncheckedOpenYearRange {
+    begin: Year,
+    resolved_end: Year,
+    form: OpenRangeForm,
+}
+
+#[cfg(feature = "serde")]
+impl From<UncheckedOpenYearRange> for OpenYearRange {
+    fn from(value: UncheckedOpenYearRange) -> Self {
+        Self::new(value.begin, value.resolved_end, value.form)
+    }
+}
+
+impl OpenYearRange {
+    /// Create an open range. If the reference year is before the beginning year, the beginning year is used instead.
+    pub fn new(begin: Year, reference_year: Year, form: OpenRangeForm) -> Self {
+        Self {
+            begin,
+            resolved_end: reference_year.max(begin),
+            form,
+        }
+    }
+
+    pub fn begin(&self) -> Year {
+        self.begin
+    }
+
+    /// The year this range was resolved to end at
+    pub fn resolved_end(&self) -> Year {
+        self.resolved_end
+    }
+
+    pub fn form(&self) -> &OpenRangeForm {
+        &self.form
+    }
+
+    /// Get the closed range this corresponds to, ending at the reference year
+    pub fn resolved(&self) -> YearRange {
+        YearRange::new(self.begin, self.resolved_end)
+    }
+}
+
+impl Display for OpenYearRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.form {
+            OpenRangeForm::Suffix(suffix) => write!(f, "{}{}", self.begin, suffix),
+            OpenRangeForm::Prefix(prefix) => write!(f, "{} {}", prefix, self.begin),
+        }
+    }
+}
+
+/// How the century of each year in a parsed `YearSpec` was worked out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum YearProvenance {
+    /// Every year was written with four digits: "1998-2003"
+    FourDigits,
+    /// A two-digit year took its century from the four-digit year at the other end of the range: "1998-03"
+    CenturyFromNeighbour,
+    /// Two-digit years were placed in the two-digit year window, with nothing to imply a century: "'98" or "98-99"
+    CenturyGuessed,
+    /// A range of two-digit years ending lower than it began was assumed to span the turn of the century: "98-03"
+    Y2kSpanAssumed,
+}
+
+impl YearProvenance {
+    /// Did working out the years rely on a heuristic?
+    pub fn is_heuristic(&self) -> bool {
+        *self != YearProvenance::FourDigits
+    }
+}
+
+impl Display for YearProvenance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            YearProvenance::FourDigits => write!(f, "written with four digits"),
+            YearProvenance::CenturyFromNeighbour => {
+                write!(f, "century taken from the other end of the range")
+            }
+            YearProvenance::CenturyGuessed => write!(f, "century guessed"),
+            YearProvenance::Y2kSpanAssumed => {
+                write!(f, "assumed to span the turn of the century")
+            }
+        }
+    }
+}
+
+/// A `YearSpec` as parsed, along with how its years were worked out.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TracedYearSpec {
+    pub spec: YearSpec,
+    pub provenance: YearProvenance,
+}
+
+impl Display for TracedYearSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.spec, self.provenance)
+    }
+}
+
+/// How to output open-ended year ranges
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OpenRangeStyle {
+    /// As they were written: "2018-present"
+    #[default]
+    AsWritten,
+    /// As a closed range ending at the reference year: "2018-2025"
+    Resolved,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 pub enum YearSpec {
     /// Just one year (2022)
     SingleYear(Year),
     /// Two years forming a range (2018-2022)
     ClosedRange(YearRange),
-    // /// An open-ended year range (2018-)
-    // OpenRange(u16),
+    /// An open-ended year range (2018-, 2018-present, since 2018)
+    OpenRange(OpenYearRange),
 }
 
 impl Display for YearSpec {
@@ -151,6 +491,7 @@ impl Display for YearSpec {
         match self {
             YearSpec::SingleYear(y) => y.fmt(f),
             YearSpec::ClosedRange(r) => r.fmt(f),
+            YearSpec::OpenRange(r) => r.fmt(f),
         }
     }
 }
@@ -158,6 +499,13 @@ impl Display for YearSpec {
 impl PartialOrd for YearSpec {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         match (self, other) {
+            // Open ranges are ordered like the closed ranges they resolve to
+            (YearSpec::OpenRange(open), _) => {
+                YearSpec::ClosedRange(open.resolved()).partial_cmp(other)
+            }
+            (_, YearSpec::OpenRange(open)) => {
+                self.partial_cmp(&YearSpec::ClosedRange(open.resolved()))
+            }
             (YearSpec::SingleYear(y), YearSpec::SingleYear(other_y)) => y.partial_cmp(other_y),
             (YearSpec::SingleYear(y), YearSpec::ClosedRange(range)) => {
                 range.partial_order_single_year(y).map(|ord| ord.reverse())
@@ -173,20 +521,64 @@ impl PartialOrd for YearSpec {
 }
 
 impl YearSpec {
-    /// Helper to more concisely construct a single year
-    pub(crate) fn single(y: u16) -> Self {
+    /// Construct a single year
+    pub fn single(y: u16) -> Self {
         Self::SingleYear(Year(y))
     }
 
+    /// Construct a closed range of years, or a single year if they are the same,
+    /// checking that it does not end before it begins.
+    pub fn try_range(begin: Year, end: Year) -> Result<Self, InvalidYearRangeError> {
+        YearRange::try_new(begin, end).map(Self::from)
+    }
+
     /// Helper to more concisely construct a closed range of years
+    #[cfg(test)]
     pub(crate) fn range(begin: Year, end: Year) -> Self {
         Self::ClosedRange(YearRange { begin, end })
     }
 
+    /// Parse a single year, closed range, or open-ended range, such as "1995-2003", "'98",
+    /// or "2018-present", with the given options.
+    pub fn parse(
+        options: impl YearRangeNormalizationOptions + Copy,
+        text: &str,
+    ) -> Result<Self, YearParseError> {
+        copyright_parsing::parse_year_spec(options, text).ok_or_else(|| {
+            let reason = diagnostics::diagnose_years(options, text, |relaxed, text| {
+                copyright_parsing::parse_year_spec(relaxed, text).is_some()
+            });
+            YearParseError::invalid(text, reason)
+        })
+    }
+
+    /// Parse a list of years and ranges, separated by commas, slashes or spaces,
+    /// such as "1995, 1998-2003", with the given options.
+    pub fn parse_list(
+        options: impl YearRangeNormalizationOptions + Copy,
+        text: &str,
+    ) -> Result<Vec<Self>, YearParseError> {
+        copyright_parsing::parse_year_spec_list(options, text).ok_or_else(|| {
+            let reason = diagnostics::diagnose_years(options, text, |relaxed, text| {
+                copyright_parsing::parse_year_spec_list(relaxed, text).is_some()
+            });
+            YearParseError::invalid(text, reason)
+        })
+    }
+
     pub fn contains(&self, other: &YearSpec) -> bool {
         match other {
             YearSpec::SingleYear(y) => self.contains_year(y),
             YearSpec::ClosedRange(r) => self.contains_range(r),
+            YearSpec::OpenRange(r) => self.contains_range(&r.resolved()),
+        }
+    }
+
+    /// Format, choosing how to output an open-ended range.
+    pub fn to_string_with(&self, style: OpenRangeStyle) -> String {
+        match (self, style) {
+            (YearSpec::OpenRange(r), OpenRangeStyle::Resolved) => r.resolved().to_string(),
+            _ => self.to_string(),
         }
     }
 }
@@ -216,6 +608,7 @@ impl YearContainment for YearSpec {
         match self {
             YearSpec::SingleYear(y) => y.contains_year(other),
             YearSpec::ClosedRange(r) => r.contains_year(other),
+            YearSpec::OpenRange(r) => r.resolved().contains_year(other),
         }
     }
 
@@ -223,6 +616,7 @@ impl YearContainment for YearSpec {
         match self {
             YearSpec::SingleYear(y) => y.contains_range(other),
             YearSpec::ClosedRange(r) => r.contains_range(other),
+            YearSpec::OpenRange(r) => r.resolved().contains_range(other),
         }
     }
 }
@@ -258,6 +652,8 @@ impl From<YearRange> for TotalOrderedYearRange {
 #[derive(Debug, Clone, Default)]
 pub struct YearRangeCollection {
     years_heap: BinaryHeap<TotalOrderedYearRange>,
+    /// The open-ended range with the latest resolved end, if any, so we can keep it open.
+    latest_open: Option<OpenYearRange>,
 }
 
 impl YearRangeCollection {
@@ -265,27 +661,98 @@ impl YearRangeCollection {
         YearRangeCollection::default()
     }
     pub fn accumulate(&mut self, year_spec: YearSpec) {
+        if let YearSpec::OpenRange(open) = &year_spec {
+            let have_later = matches!(
+                &self.latest_open,
+                Some(latest) if latest.resolved_end >= open.resolved_end
+            );
+            if !have_later {
+                self.latest_open = Some(open.clone());
+            }
+        }
         self.years_heap
             .push(TotalOrderedYearRange::from(YearRange::from(year_spec)));
     }
-    pub fn into_coalesced_vec(self) -> Vec<YearRange> {
+
+    /// Merge all the years as far as the policy allows, resolving open-ended ranges.
+    pub fn into_coalesced_vec(self, policy: CoalescingPolicy) -> Vec<YearRange> {
         coalesce_years(
             self.years_heap
                 .into_sorted_vec()
                 .into_iter()
                 .map(|tosr| tosr.0),
+            policy,
+        )
+        .collect()
+    }
+
+    /// Merge all the years into the simplest year specs, as far as the policy allows.
+    ///
+    /// A merged range that ends where an open-ended range did is kept open-ended,
+    /// unless a later year was also included.
+    pub fn into_coalesced_specs(mut self, policy: CoalescingPolicy) -> Vec<YearSpec> {
+        let latest_open = self.latest_open.take();
+        self.into_coalesced_vec(policy)
+            .into_iter()
+            .map(|range| match &latest_open {
+                Some(open) if range.end() == open.resolved_end() => {
+                    YearSpec::OpenRange(OpenYearRange {
+                        begin: range.begin(),
+                        ..open.clone()
+                    })
+                }
+                _ if range.is_single_year() => YearSpec::SingleYear(range.begin()),
+                _ => YearSpec::ClosedRange(range),
+            })
+            .collect()
+    }
+}
+
+impl YearRangeCollection {
+    /// All the years in either collection.
+    pub fn union(mut self, other: YearRangeCollection) -> Self {
+        if let Some(open) = other.latest_open {
+            self.accumulate(YearSpec::OpenRange(open));
+        }
+        self.years_heap.extend(other.years_heap);
+        self
+    }
+
+    /// The years in both collections, merged and with open-ended ranges resolved.
+    pub fn intersection(self, other: YearRangeCollection) -> Vec<YearRange> {
+        let theirs = other.into_coalesced_vec(CoalescingPolicy::Strict);
+        coalesce_years(
+            self.into_coalesced_vec(CoalescingPolicy::Strict)
+                .into_iter()
+                .flat_map(|ours| theirs.iter().filter_map(move |r| ours.intersection(r)))
+                .collect_vec(),
+            CoalescingPolicy::Strict,
         )
         .collect()
     }
+
+    /// The years in this collection but not in the other, merged and with open-ended ranges resolved.
+    pub fn subtract(self, other: YearRangeCollection) -> Vec<YearRange> {
+        let theirs = other.into_coalesced_vec(CoalescingPolicy::Strict);
+        self.into_coalesced_vec(CoalescingPolicy::Strict)
+            .into_iter()
+            .flat_map(|ours| {
+                theirs.iter().fold(vec![ours], |remaining, r| {
+                    remaining
+                        .iter()
+                        .flat_map(|piece| piece.subtract(r))
+                        .collect()
+                })
+            })
+            .collect()
+    }
 }
 
 impl Extend<YearSpec> for YearRangeCollection {
     fn extend<T: IntoIterator<Item = YearSpec>>(&mut self, iter: T) {
-        self.years_heap.extend(
-            iter.into_iter()
-                .map(YearRange::from)
-                .map(TotalOrderedYearRange::from),
-        );
+        for year_spec in iter {
+            self.accumulate(year_spec);
+        }
     }
 }
 
@@ -338,6 +805,186 @@ mod test {
         assert!(range_2023_2024.contains_range(&range_2023_2024));
     }
 
+    #[test]
+    fn open_range_collection() {
+        let open = |begin: u16| {
+            YearSpec::OpenRange(OpenYearRange::new(
+                Year(begin),
+                Year(2025),
+                OpenRangeForm::Suffix("-present".to_string()),
+            ))
+        };
+
+        let mut collection = YearRangeCollection::new();
+        collection.extend([open(2020), YearSpec::range(Year(2015), Year(2019))]);
+        collection.accumulate(YearSpec::single(2010));
+        assert_eq!(
+            collection
+                .clone()
+                .into_coalesced_specs(CoalescingPolicy::Strict)
+                .iter()
+                .map(YearSpec::to_string)
+                .collect_vec(),
+            vec!["2010", "2015-present"]
+        );
+        assert_eq!(
+            collection.into_coalesced_vec(CoalescingPolicy::Strict),
+            vec![
+                YearRange::new(Year(2010), Year(2010)),
+                YearRange::new(Year(2015), Year(2025))
+            ]
+        );
+
+        // Not open any more if something later was included
+        let mut collection = YearRangeCollection::new();
+        collection.extend([open(2020), YearSpec::single(2026)]);
+        assert_eq!(
+            collection.into_coalesced_specs(CoalescingPolicy::Strict),
+            vec![YearSpec::range(Year(2020), Year(2026))]
+        );
+
+        assert!(open(2020).contains(&YearSpec::single(2024)));
+        assert!(!YearSpec::range(Year(2020), Year(2024)).contains(&open(2020)));
+        assert!(YearSpec::range(Year(2019), Year(2025)).contains(&open(2020)));
+    }
+
+    #[test]
+    fn parse_years() {
+        use crate::{
+            raw_year::traits::SetSingleYearNormalizationOptions, CopyrightErrorReason,
+            InvalidYearRangeError,
+        };
+
+        let options = YearRangeNormalization::default();
+        assert_eq!(
+            YearRange::parse(options, "1995-2003"),
+            Ok(YearRange::new(Year(1995), Year(2003)))
+        );
+        assert_eq!(
+            YearSpec::parse_list(options, "1995, 1998-2003"),
+            Ok(vec![
+                YearSpec::single(1995),
+                YearSpec::range(Year(1998), Year(2003))
+            ])
+        );
+        assert_eq!(
+            YearSpec::parse(options, "2003-1995").unwrap_err().reason(),
+            Some(CopyrightErrorReason::RangeEndBeforeBegin)
+        );
+        assert_eq!(
+            YearRange::parse(options, "98-03").unwrap_err().reason(),
+            Some(CopyrightErrorReason::Y2kSpanNeeded)
+        );
+        assert_eq!(
+            YearRange::parse(options, "98-03").unwrap_err().to_string(),
+            "Could not parse \"98-03\" as years: two-digit year range needs to be assumed to span Y2K \
+             [y2k-span-needed] (to accept this, pass --allow-assuming-y2k-span)"
+        );
+        assert!(YearRange::parse(options.allow_century_guess(true), "98-03").is_err());
+
+        let pinned = YearRangeNormalization {
+            reference_year: Some(2025),
+            ..options
+        };
+        let open = YearRange::parse(pinned, "2018-present").unwrap_err();
+        assert_eq!(open.reason(), None);
+        assert_eq!(
+            open.to_string(),
+            "Expected a single year or closed range, but found \"2018-present\""
+        );
+        assert_eq!("2019".parse(), Ok(Year(2019)));
+        assert!("2019-2020".parse::<Year>().is_err());
+
+        assert_eq!(
+            YearSpec::try_range(Year(2019), Year(2019)),
+            Ok(YearSpec::single(2019))
+        );
+        assert_eq!(
+            YearRange::try_new(Year(2021), Year(2019)),
+            Err(InvalidYearRangeError {
+                begin: Year(2021),
+                end: Year(2019)
+            })
+        );
+    }
+
+    #[test]
+    fn range_set_operations() {
+        let range = |begin: u16, end: u16| YearRange::new(Year(begin), Year(end));
+        let collection = |ranges: &[(u16, u16)]| {
+            let mut c = YearRangeCollection::new();
+            c.extend(
+                ranges
+                    .iter()
+                    .map(|&(b, e)| YearSpec::range(Year(b), Year(e))),
+            );
+            c
+        };
+
+        assert_eq!(
+            range(2010, 2015).union(&range(2016, 2018)),
+            Some(range(2010, 2018))
+        );
+        assert_eq!(range(2010, 2015).union(&range(2017, 2018)), None);
+        // Ranges at the very ends of the years we can represent
+        let last = YearRange::try_new(Year(u16::MAX - 1), Year(u16::MAX)).unwrap();
+        let first = YearRange::try_new(Year(0), Year(1)).unwrap();
+        assert_eq!(last.union(&first), None);
+        assert_eq!(first.union(&last), None);
+        assert_eq!(
+            last.union(&range(u16::MAX - 2, u16::MAX - 2)),
+            Some(range(u16::MAX - 2, u16::MAX))
+        );
+        assert_eq!(first.union(&range(2, 2)), Some(range(0, 2)));
+        let mut edges = YearRangeCollection::new();
+        edges.extend([YearSpec::from(last), YearSpec::from(first)]);
+        assert_eq!(
+            edges.into_coalesced_vec(CoalescingPolicy::Strict),
+            vec![first, last]
+        );
+        assert_eq!(
+            range(2012, 2013).union(&range(2010, 2018)),
+            Some(range(2010, 2018))
+        );
+
+        assert_eq!(
+            range(2010, 2015).intersection(&range(2013, 2020)),
+            Some(range(2013, 2015))
+        );
+        assert_eq!(range(2010, 2015).intersection(&range(2016, 2020)), None);
+
+        assert_eq!(
+            range(2010, 2020).subtract(&range(2013, 2015)),
+            vec![range(2010, 2012), range(2016, 2020)]
+        );
+        assert_eq!(
+            range(2010, 2020).subtract(&range(2005, 2015)),
+            vec![range(2016, 2020)]
+        );
+        assert_eq!(range(2010, 2020).subtract(&range(2005, 2025)), vec![]);
+        assert_eq!(
+            range(2010, 2012).subtract(&range(2015, 2025)),
+            vec![range(2010, 2012)]
+        );
+
+        let a = collection(&[(2010, 2012), (2015, 2020)]);
+        let b = collection(&[(2011, 2016), (2019, 2019)]);
+        assert_eq!(
+            a.clone()
+                .union(b.clone())
+                .into_coalesced_vec(CoalescingPolicy::Strict),
+            vec![range(2010, 2020)]
+        );
+        assert_eq!(
+            a.clone().intersection(b.clone()),
+            vec![range(2011, 2012), range(2015, 2016), range(2019, 2019)]
+        );
+        assert_eq!(
+            a.subtract(b),
+            vec![range(2010, 2010), range(2017, 2018), range(2020, 2020)]
+        );
+    }
+
     #[test]
     fn year_spec_contains() {
         let year_2024 = Year(2024);
@@ -386,4 +1033,34 @@ mod test {
         assert!(!range_spec_2023_2024.contains_range(&range_2024_2025));
         assert!(range_spec_2023_2024.contains_range(&range_2023_2024));
     }
+
+    #[test]
+    fn coalescing_policy() {
+        let years = || {
+            let mut collection = YearRangeCollection::new();
+            collection.extend(
+                ["2019", "2021", "2022", "2026"].map(|year| year.parse::<YearSpec>().unwrap()),
+            );
+            collection
+        };
+        let coalesced = |policy| {
+            years()
+                .into_coalesced_specs(policy)
+                .iter()
+                .map(YearSpec::to_string)
+                .join(", ")
+        };
+        assert_eq!(coalesced(CoalescingPolicy::Strict), "2019, 2021-2022, 2026");
+        assert_eq!(
+            coalesced(CoalescingPolicy::MaxGap(0)),
+            "2019, 2021-2022, 2026"
+        );
+        assert_eq!(coalesced(CoalescingPolicy::MaxGap(1)), "2019-2022, 2026");
+        assert_eq!(coalesced(CoalescingPolicy::MaxGap(3)), "2019-2026");
+        assert_eq!(coalesced(CoalescingPolicy::Span), "2019-2026");
+
+        assert_eq!("span".parse(), Ok(CoalescingPolicy::Span));
+        assert_eq!("2".parse(), Ok(CoalescingPolicy::MaxGap(2)));
+        assert!("sometimes".parse::<CoalescingPolicy>().is_err());
+    }
 }
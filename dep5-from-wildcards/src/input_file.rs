@@ -6,7 +6,7 @@ use std::borrow::Borrow;
 use std::convert::TryFrom;
 use std::str::FromStr;
 
-use copyright_statements::{Copyright, YearRangeNormalization};
+use copyright_statements::{Copyright, CopyrightFormatter, YearRangeNormalization};
 use deb822_lossless::Deb822;
 use glob::Pattern;
 use itertools::Itertools;
@@ -18,6 +18,7 @@ use spdx_to_dep5::deb822::control_file::{
     SingleLineField,
 };
 use spdx_to_dep5::deb822::dep5::FilesParagraph;
+use spdx_to_dep5::license_expression::canonicalize;
 
 /// Corresponds to a `[[wildcards]]` entry in the TOML file.
 #[derive(Deserialize)]
@@ -37,6 +38,17 @@ pub struct CopyrightFileIntro {
     comment: Option<String>,
 }
 
+impl CopyrightFileIntro {
+    /// Add patterns to the Files-Excluded field, skipping any already present.
+    pub fn add_files_excluded(&mut self, patterns: impl IntoIterator<Item = String>) {
+        for pattern in patterns {
+            if !self.files_excluded.contains(&pattern) {
+                self.files_excluded.push(pattern);
+            }
+        }
+    }
+}
+
 impl Paragraph for CopyrightFileIntro {
     fn try_to_string(&self) -> Result<Option<String>, ControlFileError> {
         Ok(Some(
@@ -98,6 +110,8 @@ struct RawWildcardsFile {
 pub struct WildcardEntry {
     patterns: Vec<Pattern>,
     license: SpdxExpression,
+    /// Canonical form of `license`, for comparisons
+    canonical_license: SpdxExpression,
     copyright: Copyright,
     comment: Option<String>,
 }
@@ -121,10 +135,16 @@ impl WildcardEntry {
             .map(|w| Pattern::new(w))
             .collect::<Result<Vec<_>, _>>()?;
         let license = SpdxExpression::parse(&raw.license)?;
+        let canonical_license = canonicalize(&license);
+        // Lines that do not decompose are kept as free text, but are probably typos
+        for error in Copyright::diagnose(options, &raw.copyright) {
+            eprintln!("Copyright for {}: {error}", raw.patterns.join(" "));
+        }
         let copyright = Copyright::try_parse(options, &raw.copyright)?;
         Ok(WildcardEntry {
             patterns: wildcard,
             license,
+            canonical_license,
             copyright,
             comment: raw.comment,
         })
@@ -134,8 +154,7 @@ impl WildcardEntry {
     /// Returns true if it matches.
     pub fn matches(&self, filename: &str, license: &SpdxExpression, copyright: &Copyright) -> bool {
         self.patterns.iter().any(|p| p.matches(filename))
-            && *license == self.license
-            && self.copyright.contains(copyright)
+            && self.matches_license_and_copyright(license, copyright)
     }
 
     pub fn matches_wildcard(&self, filename: &str) -> bool {
@@ -150,31 +169,34 @@ impl WildcardEntry {
             .any(|p| p.matches(filename) && !p.to_string().contains("*"))
     }
 
+    /// Compare license and copyright data for a given file. The license expressions are compared
+    /// in canonical form, so operand order does not matter.
     pub fn matches_license_and_copyright(
         &self,
         license: &SpdxExpression,
         copyright: &Copyright,
     ) -> bool {
-        *license == self.license && self.copyright.contains(copyright)
+        canonicalize(license) == self.canonical_license && self.copyright.contains(copyright)
     }
-}
 
-/// Convert a `WildcardEntry` into a `FilesParagraph` to output for the `copyright` file
-impl From<WildcardEntry> for FilesParagraph {
-    fn from(val: WildcardEntry) -> Self {
-        let files = val
+    /// Convert into a `FilesParagraph` to output for the `copyright` file,
+    /// writing the copyright statement with the formatter if given.
+    pub fn into_paragraph(self, formatter: Option<&CopyrightFormatter>) -> FilesParagraph {
+        let files = self
             .patterns
             .iter()
             .map(ToString::to_string)
             .join("\n")
             .into();
-        let license = val.license.to_string().licenses_spdx_to_debian().into();
-        let copyright = val.copyright.to_string().into();
+        let license = self.license.to_string().licenses_spdx_to_debian().into();
+        let copyright = formatter
+            .map_or_else(|| self.copyright.to_string(), |f| f.format(&self.copyright))
+            .into();
         FilesParagraph {
             files,
             license,
             copyright,
-            comment: val.comment.map(|c| c.into()),
+            comment: self.comment.map(|c| c.into()),
         }
     }
 }
@@ -270,7 +292,8 @@ pub fn load_config(
         .wildcards
         .into_iter()
         .map(|raw| WildcardEntry::try_parse(*opts, raw))
-        .collect::<Result<Vec<WildcardEntry>, _>>()?;
+        .collect::<Result<Vec<WildcardEntry>, _>>()
+        .map_err(|e| anyhow::anyhow!("{filename}: {e}"))?;
     Ok(ParsedData {
         intro: raw.intro,
         wildcard_entries,
@@ -2,7 +2,7 @@
 //
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 use clap::{crate_authors, crate_description, Parser};
-use copyright_statements::{Copyright, YearRangeNormalization};
+use copyright_statements::{Copyright, HolderAliases, YearRangeNormalization};
 
 use input_file::WildcardEntry;
 use itertools::Itertools;
@@ -12,12 +12,18 @@ use spdx_rs::{
 };
 use spdx_to_dep5::{
     cleanup::cleanup_copyright_text,
-    cli_help::omit_or_normalize_none,
-    deb822::{
-        control_file::{Paragraph, Paragraphs},
-        dep5::FilesParagraph,
+    cli_help::{
+        omit_or_normalize_none, report_alias_resolutions, report_heuristic_years,
+        report_implausible_years, report_missing_licenses, report_policy_violations,
+        CopyrightFormatArgs, YearNormalizationArgs, YearPlausibilityArgs,
     },
-    tree::{make_paragraphs, CopyrightDataTree},
+    deb822::control_file::{Paragraph, Paragraphs},
+    holder_aliases::load_holder_aliases,
+    license_policy::LicensePolicy,
+    license_selection::{
+        InFileCombination, LicenseSelection, MissingLicenseAction, SelectedLicense,
+    },
+    tree::{make_paragraphs, CopyrightDataTree, ImplausibleYearAction, TreeOptions},
 };
 
 use crate::input_file::{load_config, CopyrightFileIntro};
@@ -27,21 +33,14 @@ mod input_file;
 #[derive(Parser, Debug)]
 #[command(author=crate_authors!(), version, about=crate_description!())]
 struct Args {
-    /// Should allow the century to be guessed entirely when there is no four-digit year
-    /// suitably close to imply a century?
-    #[arg(long = "allow-century-guess", action)]
-    allow_century_guess: bool,
-
-    /// If both years of a range are two-digit years, and the second is smaller than the first,
-    /// can we assume the years span Y2K? This is a reasonable assumption as long as you are working
-    /// with computer software in the 21st century.
-    #[arg(long, action)]
-    allow_assuming_y2k_span: bool,
-
-    /// Should we allow the century part of a year range's endpoint to be inferred
-    /// across a century boundary based on the other endpoint's known century.
-    #[arg(long, action)]
-    allow_mixed_size_implied_century_rollover: bool,
+    #[command(flatten)]
+    year_options: YearNormalizationArgs,
+
+    #[command(flatten)]
+    copyright_format: CopyrightFormatArgs,
+
+    #[command(flatten)]
+    year_plausibility: YearPlausibilityArgs,
 
     /// SPDX Input file
     #[arg(default_value = "summary.spdx")]
@@ -58,47 +57,58 @@ struct Args {
     /// Simplify by combining uniformly-licensed subtrees
     #[arg(short, long)]
     simplify: bool,
-}
 
-/// Turn the expressions in the file into a OR expression.
-fn info_in_file_to_expression(license_info_in_file: &[SpdxExpression]) -> SpdxExpression {
-    let s = license_info_in_file
-        .iter()
-        .unique()
-        // .map(|e| format!("({})", e))
-        .map(ToString::to_string)
-        .sorted()
-        .join(" or ");
-    if let Ok(e) = SpdxExpression::parse(&s) {
-        e
-    } else {
-        // TODO this is a fallback in case of error
-        license_info_in_file.first().cloned().unwrap_or_default()
-    }
+    /// Which license field(s) of each file to use
+    #[arg(long, value_enum, default_value_t = LicenseSelection::ConcludedIfAsserted)]
+    license_source: LicenseSelection,
+
+    /// How to combine multiple license expressions found in a single file
+    #[arg(long, value_enum, default_value_t)]
+    in_file_combination: InFileCombination,
+
+    /// Mention which license field(s) were used in the comment of each generated paragraph
+    #[arg(long)]
+    comment_license_source: bool,
+
+    /// TOML file listing allowed, denied, and needing-review licenses to check every file against
+    #[arg(long)]
+    license_policy: Option<String>,
+
+    /// File mapping variant copyright holders to canonical ones: in git .mailmap syntax,
+    /// or TOML if the name ends in .toml
+    #[arg(long)]
+    holder_aliases: Option<String>,
+
+    /// Add patterns for files with denied licenses to the Files-Excluded field of the header
+    #[arg(long, requires = "license_policy")]
+    files_excluded: bool,
+
+    /// What to do with files whose license is NOASSERTION or NONE
+    #[arg(long, value_enum, default_value_t)]
+    missing_license: MissingLicenseAction,
+
+    /// License expression for files whose license is NOASSERTION or NONE, with `--missing-license default`
+    #[arg(long, value_parser = SpdxExpression::parse, required_if_eq("missing_license", "default"))]
+    default_license: Option<SpdxExpression>,
 }
 
 /// Compare a file's information against a collection of wildcards
 fn matches_wildcards(
     options: YearRangeNormalization,
+    holder_aliases: &HolderAliases,
     wildcards: &[WildcardEntry],
     item: &FileInformation,
+    license_to_match: &SpdxExpression,
 ) -> bool {
-    let license_to_match = item
-        .concluded_license
-        .as_ref()
-        .and_then(|concluded| {
-            if *concluded != SpdxExpression::default() {
-                Some(concluded.clone())
-            } else {
-                None
-            }
-        })
-        .unwrap_or_else(|| info_in_file_to_expression(&item.license_information_in_file));
-
     let copyright_text = cleanup_copyright_text(&item.copyright_text).join("\n");
     let filename = item.file_name.trim_start_matches("./");
 
-    let parsed_copyright = Copyright::try_parse(options, &copyright_text);
+    for error in Copyright::diagnose(options, &copyright_text) {
+        eprintln!("{filename}: {error}");
+    }
+    let parsed_copyright =
+        Copyright::try_parse_with_aliases(options, holder_aliases, &copyright_text)
+            .map(|(copyright, _)| copyright);
 
     if let Ok(copyright) = parsed_copyright {
         let matching_wildcard = wildcards
@@ -107,7 +117,7 @@ fn matches_wildcards(
             .last();
         if let Some(wildcard) = matching_wildcard {
             // we matched a "wildcard", if it is actually an exact filename, we better match license and copyright.
-            let match_lic = wildcard.matches_license_and_copyright(&license_to_match, &copyright);
+            let match_lic = wildcard.matches_license_and_copyright(license_to_match, &copyright);
             if wildcard.matches_exact_pattern(filename) && !match_lic {
                 eprintln!(
                     "License/copyright mismatch for a file matching exact wildcard: {}",
@@ -118,19 +128,18 @@ fn matches_wildcards(
         }
         return false;
     }
-    eprintln!("{}: parse copyright failed", filename);
+    if let Err(e) = parsed_copyright {
+        eprintln!("{filename}: {e}");
+    }
     false
 }
 
 fn main() -> Result<(), anyhow::Error> {
-    env_logger::init();
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
     let args = Args::parse();
 
-    let opts = YearRangeNormalization {
-        allow_century_guess: args.allow_century_guess,
-        allow_assuming_y2k_span: args.allow_assuming_y2k_span,
-        allow_mixed_size_implied_century_rollover: args.allow_mixed_size_implied_century_rollover,
-    };
+    let opts = args.year_options.normalization();
+    let formatter = args.copyright_format.formatter();
 
     // load SPDX file
     let filename = args.spdx_input;
@@ -144,30 +153,113 @@ fn main() -> Result<(), anyhow::Error> {
 
     // Load TOML or dep5 copyright file
     let filename = args.wildcard_input;
-    let parsed = load_config(&filename, &opts)?;
+    let mut parsed = load_config(&filename, &opts)?;
 
     eprintln!(
         "Exclusions: {}",
         &parsed.exclude.iter().map(|p| p.as_str()).join(";")
     );
-    // Turn entries that do not match the wildcard into tree
-    let mut data_tree: CopyrightDataTree = spdx_information
+    let holder_aliases = match &args.holder_aliases {
+        Some(filename) => load_holder_aliases(filename)?,
+        None => HolderAliases::new(),
+    };
+    let tree_options = TreeOptions {
+        license_selection: args.license_source,
+        in_file_combination: args.in_file_combination,
+        comment_license_source: args.comment_license_source,
+        missing_license: args.missing_license,
+        default_license: args.default_license,
+        holder_aliases: holder_aliases.clone(),
+        year_normalization: opts,
+        year_coalescing: args.copyright_format.coalesce_years.unwrap_or_default(),
+        year_plausibility: args.year_plausibility.plausibility(&args.year_options),
+        implausible_years: args.year_plausibility.implausible_years,
+    };
+    // Select the license of each file once, for both matching and the trees
+    let spdx_information = spdx_information
         .into_iter()
         .filter(|fi| {
             let filename = fi.file_name.trim_start_matches("./");
             !parsed.exclude.iter().any(|pat| pat.matches(filename))
         })
-        .filter(|fi| !matches_wildcards(opts, &parsed.wildcard_entries, fi))
-        .collect();
+        .map(|fi| {
+            let selected = tree_options
+                .license_selection
+                .select(&fi, tree_options.in_file_combination);
+            (fi, selected)
+        })
+        .collect_vec();
+
+    let mut intro = parsed.intro.take();
+
+    // Resolve missing licenses with all files present, whether or not they match a wildcard
+    let mut full_tree = CopyrightDataTree::with_options(tree_options.clone());
+    for (fi, selected) in &spdx_information {
+        full_tree.add_file(fi, selected.clone());
+    }
+    let missing = full_tree
+        .resolve_missing_licenses()
+        .inspect_err(|e| report_missing_licenses(&e.files))?;
+    report_missing_licenses(missing);
+    // Check the years of all files, including those covered by a wildcard
+    report_heuristic_years(&full_tree.heuristic_years());
+    let implausible = full_tree
+        .implausible_years()
+        .inspect_err(|e| report_implausible_years(&e.files))?;
+    report_implausible_years(implausible);
+
+    // Check all files against the license policy
+    if let Some(policy_filename) = args.license_policy {
+        let policy = LicensePolicy::load(&policy_filename)?;
+        report_policy_violations(&full_tree.check_license_policy(&policy));
+        if args.files_excluded {
+            let patterns = full_tree.files_excluded_patterns(&policy);
+            match intro.as_mut() {
+                Some(intro) => intro.add_files_excluded(patterns),
+                None if !patterns.is_empty() => {
+                    eprintln!("No header in {filename} to add Files-Excluded patterns to")
+                }
+                None => {}
+            }
+        }
+    }
+
+    // Turn entries that do not match the wildcard into tree
+    let mut data_tree = CopyrightDataTree::with_options(TreeOptions {
+        // Already checked for the full tree
+        implausible_years: ImplausibleYearAction::Ignore,
+        ..tree_options
+    });
+    for (fi, selected) in spdx_information {
+        // Files left out of the full tree are left out of this one too
+        let Some(license) = full_tree.file_license(&fi.file_name) else {
+            continue;
+        };
+        if !matches_wildcards(
+            opts,
+            &holder_aliases,
+            &parsed.wildcard_entries,
+            &fi,
+            license,
+        ) {
+            let selected = SelectedLicense {
+                expression: license.clone(),
+                ..selected
+            };
+            data_tree.add_file(&fi, selected);
+        }
+    }
+    // Already reported for the full tree
+    data_tree.resolve_missing_licenses()?;
+    report_alias_resolutions(&data_tree.alias_resolutions());
     // identify uniformly-licensed subtrees
     if args.simplify {
         data_tree.propagate_metadata();
     }
 
     // Intro header
-    let intro: Option<String> = parsed
-        .intro
-        .and_then(|intro: CopyrightFileIntro| Paragraph::try_to_string_ok(&intro));
+    let intro: Option<String> =
+        intro.and_then(|intro: CopyrightFileIntro| Paragraph::try_to_string_ok(&intro));
 
     // Trailing licenses
     let trailing = parsed
@@ -178,14 +270,13 @@ fn main() -> Result<(), anyhow::Error> {
     let explicit_paragraphs = parsed
         .wildcard_entries
         .into_iter()
-        .map(|w| {
-            let para: FilesParagraph = w.into();
-            para
-        })
+        .map(|w| w.into_paragraph(formatter.as_ref()))
         .flatten_to_strings();
 
     // These are the ones we need to add for completeness, sorted.
-    let additional_paragraphs = make_paragraphs(data_tree).flatten_to_strings().sorted();
+    let additional_paragraphs = make_paragraphs(data_tree, formatter.as_ref(), opts)
+        .flatten_to_strings()
+        .sorted();
 
     // Everybody turns into a string
     let paragraphs: Vec<String> = intro
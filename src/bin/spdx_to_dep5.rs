@@ -1,16 +1,30 @@
 // Copyright 2021-2025, Collabora, Ltd.
 //
 // SPDX-License-Identifier: Apache-2.0 OR MIT
+use std::convert::TryFrom;
+
 use clap::{crate_authors, crate_description, ArgGroup, Parser};
+use copyright_statements::{cluster_holders, HolderAliases, HolderClusterOptions};
 use itertools::Itertools;
-use spdx_rs::{models::FileInformation, parsers::spdx_from_tag_value};
+use spdx_rs::{
+    models::{FileInformation, SpdxExpression},
+    parsers::spdx_from_tag_value,
+};
 use spdx_to_dep5::{
-    cli_help::omit_or_normalize_none,
+    cli_help::{
+        omit_or_normalize_none, report_alias_resolutions, report_copyright_diagnostics,
+        report_heuristic_years, report_holder_clusters, report_implausible_years,
+        report_missing_licenses, report_policy_violations, CopyrightFormatArgs, ReportMode,
+        YearNormalizationArgs, YearPlausibilityArgs,
+    },
     deb822::{
-        control_file::{Paragraph, Paragraphs},
+        control_file::{MultilineEmptyFirstLineField, Paragraph, Paragraphs},
         dep5::HeaderParagraph,
     },
-    tree::{make_paragraphs, CopyrightDataTree},
+    holder_aliases::load_holder_aliases,
+    license_policy::LicensePolicy,
+    license_selection::{InFileCombination, LicenseSelection, MissingLicenseAction},
+    tree::{make_paragraphs, CopyrightDataTree, TreeOptions},
 };
 
 #[derive(Parser, Debug)]
@@ -36,21 +50,57 @@ struct Args {
     #[arg(short, long)]
     omit_no_copyright: bool,
 
-    /// Should allow the century to be guessed entirely when there is no four-digit year
-    /// suitably close to imply a century?
+    #[command(flatten)]
+    year_options: YearNormalizationArgs,
+
+    #[command(flatten)]
+    copyright_format: CopyrightFormatArgs,
+
+    #[command(flatten)]
+    year_plausibility: YearPlausibilityArgs,
+
+    /// Which license field(s) of each file to use
+    #[arg(long, value_enum, default_value_t)]
+    license_source: LicenseSelection,
+
+    /// How to combine multiple license expressions found in a single file
+    #[arg(long, value_enum, default_value_t)]
+    in_file_combination: InFileCombination,
+
+    /// Mention which license field(s) were used in the comment of each paragraph
     #[arg(long)]
-    allow_century_guess: bool,
+    comment_license_source: bool,
 
-    /// If both years of a range are two-digit years, and the second is smaller than the first,
-    /// can we assume the years span Y2K? This is a reasonable assumption as long as you are working
-    /// with computer software in the 21st century.
+    /// TOML file with `allow`, `deny`, and `review` lists of licenses to check every file against
     #[arg(long)]
-    allow_assuming_y2k_span: bool,
+    license_policy: Option<String>,
 
-    /// Should we allow the century part of a year range's endpoint to be inferred
-    /// across a century boundary based on the other endpoint's known century.
+    /// File mapping variant copyright holders to canonical ones: in git .mailmap syntax,
+    /// or TOML if the name ends in .toml
     #[arg(long)]
-    allow_mixed_size_implied_century_rollover: bool,
+    holder_aliases: Option<String>,
+
+    /// Print a report instead of the copyright file
+    #[arg(long, value_enum)]
+    report: Option<ReportMode>,
+
+    /// With `--report holders`, the most single-character edits between holder names
+    /// (ignoring case, punctuation, and corporate suffixes) for them to be grouped
+    #[arg(long, default_value_t = HolderClusterOptions::default().max_edit_distance)]
+    holder_edit_distance: usize,
+
+    /// Add a Files-Excluded field listing patterns that would remove all files
+    /// with licenses denied by the license policy
+    #[arg(long, requires = "license_policy")]
+    files_excluded: bool,
+
+    /// What to do with files whose license is NOASSERTION or NONE
+    #[arg(long, value_enum, default_value_t)]
+    missing_license: MissingLicenseAction,
+
+    /// License expression for files whose license is NOASSERTION or NONE, with `--missing-license default`
+    #[arg(long, value_parser = SpdxExpression::parse, required_if_eq("missing_license", "default"))]
+    default_license: Option<SpdxExpression>,
 }
 
 /// Filter files according to arguments (at most one of `exclude` and `include` may be non-empty)
@@ -59,20 +109,21 @@ fn filter_files(
     iter: impl Iterator<Item = FileInformation>,
     exclude: Vec<String>,
     include: Vec<String>,
+    options: TreeOptions,
 ) -> CopyrightDataTree {
+    let mut tree = CopyrightDataTree::with_options(options);
     if !exclude.is_empty() {
-        iter.filter(|f| !exclude.iter().any(|ext| f.file_name.ends_with(ext)))
-            .collect()
+        tree.extend(iter.filter(|f| !exclude.iter().any(|ext| f.file_name.ends_with(ext))));
     } else if !include.is_empty() {
-        iter.filter(|f| include.iter().any(|ext| f.file_name.ends_with(ext)))
-            .collect()
+        tree.extend(iter.filter(|f| include.iter().any(|ext| f.file_name.ends_with(ext))));
     } else {
-        iter.collect()
+        tree.extend(iter);
     }
+    tree
 }
 
-fn main() -> Result<(), spdx_rs::error::SpdxError> {
-    env_logger::init();
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
     let args = Args::parse();
 
     // load SPDX file
@@ -86,16 +137,73 @@ fn main() -> Result<(), spdx_rs::error::SpdxError> {
     let spdx_information: Vec<_> =
         omit_or_normalize_none(doc.file_information, args.omit_no_copyright);
 
+    let holder_aliases = match &args.holder_aliases {
+        Some(filename) => load_holder_aliases(filename)?,
+        None => HolderAliases::new(),
+    };
+    let options = TreeOptions {
+        license_selection: args.license_source,
+        in_file_combination: args.in_file_combination,
+        comment_license_source: args.comment_license_source,
+        missing_license: args.missing_license,
+        default_license: args.default_license,
+        holder_aliases,
+        year_normalization: args.year_options.normalization(),
+        year_coalescing: args.copyright_format.coalesce_years.unwrap_or_default(),
+        year_plausibility: args.year_plausibility.plausibility(&args.year_options),
+        implausible_years: args.year_plausibility.implausible_years,
+    };
+
     // Turn into tree, and identify uniformly-licensed subtrees
-    let mut tree: CopyrightDataTree =
-        filter_files(spdx_information.into_iter(), args.exclude, args.include);
+    let mut tree: CopyrightDataTree = filter_files(
+        spdx_information.into_iter(),
+        args.exclude,
+        args.include,
+        options,
+    );
+    let missing = tree
+        .resolve_missing_licenses()
+        .inspect_err(|e| report_missing_licenses(&e.files))?;
+    report_missing_licenses(missing);
+    report_alias_resolutions(&tree.alias_resolutions());
+    report_copyright_diagnostics(tree.copyright_diagnostics());
+    report_heuristic_years(&tree.heuristic_years());
+    let implausible = tree
+        .implausible_years()
+        .inspect_err(|e| report_implausible_years(&e.files))?;
+    report_implausible_years(implausible);
+    if args.report == Some(ReportMode::Holders) {
+        let options = HolderClusterOptions {
+            max_edit_distance: args.holder_edit_distance,
+        };
+        report_holder_clusters(&cluster_holders(tree.holder_file_counts(), options));
+        return Ok(());
+    }
     tree.propagate_metadata();
 
+    let mut header = HeaderParagraph::default();
+    if let Some(policy_filename) = &args.license_policy {
+        let policy = LicensePolicy::load(policy_filename)?;
+        report_policy_violations(&tree.check_license_policy(&policy));
+        if args.files_excluded {
+            let patterns = tree.files_excluded_patterns(&policy);
+            header.files_excluded = MultilineEmptyFirstLineField::try_from(&patterns).ok();
+        }
+    }
+
     // Turn into debian copyright file paragraphs
-    let paragraphs: Vec<String> = HeaderParagraph::default()
+    let paragraphs: Vec<String> = header
         .try_to_string_ok()
         .into_iter()
-        .chain(make_paragraphs(tree).flatten_to_strings().sorted())
+        .chain(
+            make_paragraphs(
+                tree,
+                args.copyright_format.formatter().as_ref(),
+                args.year_options.normalization(),
+            )
+            .flatten_to_strings()
+            .sorted(),
+        )
         .collect();
     println!("{}", paragraphs.join("\n\n"));
     Ok(())
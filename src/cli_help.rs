@@ -2,8 +2,172 @@
 //
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
+use copyright_statements::{
+    AliasResolution, CoalescingPolicy, CopyrightFormatter, CopyrightPrefix, HolderCluster,
+    HolderOrder, YearCompaction, YearPlausibility, YearRangeNormalization,
+};
 use spdx_rs::models::FileInformation;
 
+use crate::tree::{
+    CopyrightDiagnosticsFile, HeuristicYears, ImplausibleYearAction, ImplausibleYearFile,
+    MissingLicenseFile, PolicyViolation,
+};
+
+/// Command-line options for how years in copyright statements are interpreted,
+/// shared by all binaries.
+#[derive(clap::Args, Debug, Clone)]
+pub struct YearNormalizationArgs {
+    /// Should allow the century to be guessed entirely when there is no four-digit year
+    /// suitably close to imply a century?
+    #[arg(long, action)]
+    pub allow_century_guess: bool,
+
+    /// If both years of a range are two-digit years, and the second is smaller than the first,
+    /// can we assume the years span Y2K? This is a reasonable assumption as long as you are working
+    /// with computer software in the 21st century.
+    #[arg(long, action)]
+    pub allow_assuming_y2k_span: bool,
+
+    /// Should we allow the century part of a year range's endpoint to be inferred
+    /// across a century boundary based on the other endpoint's known century.
+    #[arg(long, action)]
+    pub allow_mixed_size_implied_century_rollover: bool,
+
+    /// Should "2019/2020" be a range including any years in between,
+    /// rather than a list of just those two years?
+    #[arg(long, action)]
+    pub allow_slash_ranges: bool,
+
+    /// Must a two-digit year standing alone be written with an apostrophe ('98)
+    /// to be recognized as a year?
+    #[arg(long, action)]
+    pub require_apostrophe_for_two_digit_years: bool,
+
+    /// The year that open-ended ranges like "2018-present" extend to, and that
+    /// --two-digit-year-pivot is relative to. Defaults to the current year.
+    #[arg(long)]
+    pub reference_year: Option<u16>,
+
+    /// Take two-digit years to be at most this many years after the reference year,
+    /// and otherwise in the past. By default, 00-59 are 2000-2059 and 60-99 are 1960-1999.
+    #[arg(long)]
+    pub two_digit_year_pivot: Option<u16>,
+
+    /// The earliest four-digit year to recognize. Defaults to 1900.
+    #[arg(long)]
+    pub earliest_year: Option<u16>,
+
+    /// The latest four-digit year to recognize. Defaults to 2099.
+    #[arg(long)]
+    pub latest_year: Option<u16>,
+}
+
+impl YearNormalizationArgs {
+    pub fn normalization(&self) -> YearRangeNormalization {
+        YearRangeNormalization {
+            allow_century_guess: self.allow_century_guess,
+            allow_assuming_y2k_span: self.allow_assuming_y2k_span,
+            allow_mixed_size_implied_century_rollover: self
+                .allow_mixed_size_implied_century_rollover,
+            allow_slash_ranges: self.allow_slash_ranges,
+            require_apostrophe_for_two_digit_years: self.require_apostrophe_for_two_digit_years,
+            reference_year: self.reference_year,
+            two_digit_year_pivot: self.two_digit_year_pivot,
+            earliest_year: self.earliest_year,
+            latest_year: self.latest_year,
+        }
+    }
+}
+
+/// Command-line options for which years in copyright statements are plausible,
+/// shared by the binaries that build a tree.
+#[derive(clap::Args, Debug, Clone)]
+pub struct YearPlausibilityArgs {
+    /// Report years before this, such as the year the project started, as implausible.
+    /// Defaults to 1970.
+    #[arg(long)]
+    pub project_start_year: Option<u16>,
+
+    /// What to do with files whose copyright statements have implausible years:
+    /// before --project-start-year or after --reference-year (by default, the current year)
+    #[arg(long, value_enum, default_value_t)]
+    pub implausible_years: ImplausibleYearAction,
+}
+
+impl YearPlausibilityArgs {
+    pub fn plausibility(&self, year_options: &YearNormalizationArgs) -> YearPlausibility {
+        YearPlausibility {
+            reference_year: year_options.reference_year,
+            earliest_year: self.project_start_year,
+        }
+    }
+}
+
+/// Command-line options for how copyright statements are written in generated paragraphs,
+/// shared by the binaries that generate them.
+///
+/// If none of these are passed, copyright statements are output as found.
+#[derive(clap::Args, Debug, Clone)]
+pub struct CopyrightFormatArgs {
+    /// What to put before each copyright line: none, copyright ("Copyright (C)"),
+    /// or spdx ("SPDX-FileCopyrightText:")
+    #[arg(long)]
+    pub copyright_prefix: Option<CopyrightPrefix>,
+
+    /// What to put between the years and the holder. Defaults to ", ".
+    #[arg(long)]
+    pub copyright_separator: Option<String>,
+
+    /// How to output the years of each copyright line: list (every year and range),
+    /// span (first to last year), or omit
+    #[arg(long)]
+    pub copyright_years: Option<YearCompaction>,
+
+    /// How far apart years may be and still be merged into one range, in the output and when
+    /// grouping equivalent statements: strict (only adjacent years), span (first to last year),
+    /// or the number of missing years to bridge
+    #[arg(long)]
+    pub coalesce_years: Option<CoalescingPolicy>,
+
+    /// In what order to output copyright holders: as-written, alphabetical, or chronological
+    #[arg(long)]
+    pub holder_order: Option<HolderOrder>,
+}
+
+impl CopyrightFormatArgs {
+    /// The formatter to use, if any formatting option was passed.
+    pub fn formatter(&self) -> Option<CopyrightFormatter> {
+        if self.copyright_prefix.is_none()
+            && self.copyright_separator.is_none()
+            && self.copyright_years.is_none()
+            && self.coalesce_years.is_none()
+            && self.holder_order.is_none()
+        {
+            return None;
+        }
+        let default = CopyrightFormatter::default();
+        Some(CopyrightFormatter {
+            prefix: self.copyright_prefix.unwrap_or(default.prefix),
+            separator: self
+                .copyright_separator
+                .clone()
+                .unwrap_or(default.separator),
+            years: self.copyright_years.unwrap_or(default.years),
+            coalescing: self.coalesce_years,
+            holder_order: self.holder_order.unwrap_or(default.holder_order),
+            ..default
+        })
+    }
+}
+
+/// A report to print instead of the generated copyright file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, clap::ValueEnum)]
+pub enum ReportMode {
+    /// Groups of copyright holders that look like variants of each other, in .mailmap syntax
+    /// for use with --holder-aliases
+    Holders,
+}
+
 fn is_copyright_text_empty(fi: &FileInformation) -> bool {
     match &fi.copyright_text {
         None => true,
@@ -33,6 +197,107 @@ fn normalize_no_copyright(file_info: Vec<FileInformation>) -> Vec<FileInformatio
         .collect()
 }
 
+/// Print files whose license does not satisfy a license policy to stderr.
+pub fn report_policy_violations(violations: &[PolicyViolation]) {
+    for violation in violations {
+        eprintln!(
+            "{}: license {} is {}",
+            violation.file, violation.license, violation.verdict
+        );
+    }
+    if !violations.is_empty() {
+        eprintln!(
+            "{} files have licenses not allowed by the license policy",
+            violations.len()
+        );
+    }
+}
+
+/// Print files whose license was "NOASSERTION" or "NONE", and what became of them, to stderr.
+pub fn report_missing_licenses(files: &[MissingLicenseFile]) {
+    for file in files {
+        eprintln!(
+            "{}: license is {}, {}",
+            file.file, file.license, file.outcome
+        );
+    }
+    if !files.is_empty() {
+        eprintln!(
+            "{} files have a license of NOASSERTION or NONE",
+            files.len()
+        );
+    }
+}
+
+pub fn report_alias_resolutions(resolutions: &[(&AliasResolution, usize)]) {
+    for (resolution, count) in resolutions {
+        eprintln!("Holder alias applied to {count} files: {resolution}");
+    }
+}
+
+/// Print why lines of copyright statements could not be decomposed to stderr, for review.
+pub fn report_copyright_diagnostics(files: &[CopyrightDiagnosticsFile]) {
+    for file in files {
+        for error in &file.errors {
+            eprintln!("{}: {error}", file.file);
+        }
+    }
+}
+
+/// Print files whose copyright statements have implausible years to stderr, for review.
+pub fn report_implausible_years(files: &[ImplausibleYearFile]) {
+    for file in files {
+        eprintln!(
+            "{}: implausible years in copyright statement: {}",
+            file.file,
+            file.errors
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+    if !files.is_empty() {
+        eprintln!(
+            "{} files have implausible years in their copyright statements",
+            files.len()
+        );
+    }
+}
+
+/// Print copyright statements with years that were worked out using heuristics to stderr, for review.
+pub fn report_heuristic_years(statements: &[&HeuristicYears]) {
+    for statement in statements {
+        eprintln!(
+            "Years worked out using heuristics in {} files, such as {}:",
+            statement.files.len(),
+            statement.files[0]
+        );
+        for line in statement.statement.lines() {
+            eprintln!("    {line}");
+        }
+        for year in &statement.years {
+            eprintln!("  {year}");
+        }
+    }
+    if !statements.is_empty() {
+        eprintln!(
+            "{} copyright statements have years worked out using heuristics",
+            statements.len()
+        );
+    }
+}
+
+/// Print groups of similar copyright holders to stdout, ready to paste into an alias file.
+pub fn report_holder_clusters(clusters: &[HolderCluster]) {
+    if clusters.is_empty() {
+        eprintln!("No similar copyright holders found");
+    }
+    for cluster in clusters {
+        println!("{cluster}");
+    }
+}
+
 pub fn omit_or_normalize_none(
     file_info: Vec<FileInformation>,
     omit_missing_copyright: bool,
@@ -7,7 +7,7 @@
 //! See <https://dep-team.pages.debian.net/deps/dep5>
 
 use crate::deb822::control_file::{
-    MultilineField, Paragraph, ParagraphAccumulator, SingleLineField,
+    MultilineEmptyFirstLineField, MultilineField, Paragraph, ParagraphAccumulator, SingleLineField,
     SingleLineOrMultilineEmptyFirstLineField,
 };
 
@@ -18,6 +18,7 @@ pub struct HeaderParagraph {
     pub upstream_name: Option<SingleLineField>,
     pub upstream_contact: Option<SingleLineField>,
     pub source: Option<SingleLineField>,
+    pub files_excluded: Option<MultilineEmptyFirstLineField>,
     pub disclaimer: Option<SingleLineOrMultilineEmptyFirstLineField>,
     pub comment: Option<SingleLineOrMultilineEmptyFirstLineField>,
     pub license: Option<SingleLineOrMultilineEmptyFirstLineField>,
@@ -33,6 +34,7 @@ impl Default for HeaderParagraph {
             upstream_name: None,
             upstream_contact: None,
             source: None,
+            files_excluded: None,
             disclaimer: None,
             comment: None,
             license: None,
@@ -51,6 +53,7 @@ impl Paragraph for HeaderParagraph {
                 .write("Upstream-Name", &self.upstream_name)?
                 .write("Upstream-Contact", &self.upstream_contact)?
                 .write("Source", &self.source)?
+                .write("Files-Excluded", &self.files_excluded)?
                 .write("Disclaimer", &self.disclaimer)?
                 .write("Comment", &self.comment)?
                 .write("License", &self.license)?
@@ -0,0 +1,121 @@
+// Copyright 2025, Collabora, Ltd.
+//
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Loading copyright holder aliases from a `.mailmap`-style file or a TOML file.
+
+use copyright_statements::{Holder, HolderAlias, HolderAliases, MailmapError};
+use serde::Deserialize;
+
+#[derive(Debug, thiserror::Error)]
+pub enum HolderAliasError {
+    #[error("Could not read holder aliases: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse holder aliases: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("Could not parse holder aliases: {0}")]
+    Mailmap(#[from] MailmapError),
+}
+
+/// A canonical holder and the variants that should be replaced by it, in a TOML file:
+///
+/// ```toml
+/// [[holder]]
+/// name = "Collabora, Ltd."
+/// email = "info@collabora.com"
+/// aliases = ["Collabora Ltd", "Collabora Ltd. <info@collabora.co.uk>"]
+/// emails = ["info@collabora.co.uk"]
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+struct CanonicalHolder {
+    /// The canonical name, if it should replace the name as written
+    name: Option<String>,
+    /// The canonical e-mail, if it should replace the e-mail as written
+    email: Option<String>,
+    /// Holders as written, with an optional e-mail which must also match
+    #[serde(default)]
+    aliases: Vec<String>,
+    /// E-mails to match, whatever the name
+    #[serde(default)]
+    emails: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct HolderAliasFile {
+    #[serde(default)]
+    holder: Vec<CanonicalHolder>,
+}
+
+impl From<HolderAliasFile> for HolderAliases {
+    fn from(file: HolderAliasFile) -> Self {
+        let mut aliases = HolderAliases::new();
+        for canonical in file.holder {
+            let make_alias = |match_name, match_email| HolderAlias {
+                name: canonical.name.clone(),
+                email: canonical.email.clone(),
+                match_name,
+                match_email,
+            };
+            for alias in &canonical.aliases {
+                let holder = Holder::parse(alias);
+                aliases.add(make_alias(Some(holder.name), holder.email));
+            }
+            for email in &canonical.emails {
+                aliases.add(make_alias(None, Some(email.clone())));
+            }
+        }
+        aliases
+    }
+}
+
+/// Parse holder aliases from a TOML file, see `CanonicalHolder`.
+pub fn parse_toml_holder_aliases(text: &str) -> Result<HolderAliases, HolderAliasError> {
+    Ok(toml::from_str::<HolderAliasFile>(text)?.into())
+}
+
+/// Load holder aliases from a file: TOML if the name ends in `.toml`, otherwise `.mailmap` syntax
+/// (see `HolderAliases::parse_mailmap`).
+pub fn load_holder_aliases(filename: &str) -> Result<HolderAliases, HolderAliasError> {
+    let text = std::fs::read_to_string(filename)?;
+    if filename.ends_with(".toml") {
+        parse_toml_holder_aliases(&text)
+    } else {
+        Ok(HolderAliases::parse_mailmap(&text)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use copyright_statements::Holder;
+
+    use super::parse_toml_holder_aliases;
+
+    #[test]
+    fn toml_aliases() {
+        let aliases = parse_toml_holder_aliases(
+            r#"
+[[holder]]
+name = "Collabora, Ltd."
+aliases = ["Collabora Ltd", "Collabora Ltd. <info@collabora.com>"]
+emails = ["info@collabora.co.uk"]
+"#,
+        )
+        .unwrap();
+        let resolve = |holder: &str| {
+            aliases
+                .resolve(&Holder::parse(holder))
+                .map(|h| h.to_string())
+        };
+        assert_eq!(resolve("Collabora Ltd").as_deref(), Some("Collabora, Ltd."));
+        assert_eq!(
+            resolve("Collabora Ltd. <info@collabora.com>").as_deref(),
+            Some("Collabora, Ltd. <info@collabora.com>")
+        );
+        // The e-mail must match too, when given
+        assert_eq!(resolve("Collabora Ltd. <sales@collabora.com>"), None);
+        assert_eq!(
+            resolve("Someone <info@collabora.co.uk>").as_deref(),
+            Some("Collabora, Ltd. <info@collabora.co.uk>")
+        );
+    }
+}
@@ -5,4 +5,8 @@
 pub mod cleanup;
 pub mod cli_help;
 pub mod deb822;
+pub mod holder_aliases;
+pub mod license_expression;
+pub mod license_policy;
+pub mod license_selection;
 pub mod tree;
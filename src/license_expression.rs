@@ -0,0 +1,485 @@
+// Copyright 2025, Collabora, Ltd.
+//
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Canonical forms of SPDX license expressions, so that equivalent expressions compare equal.
+//!
+//! The canonical form flattens nested operators of the same kind, sorts and de-duplicates operands,
+//! and applies absorption (`A AND (A OR B)` is `A`, `A OR (A AND B)` is `A`).
+//!
+//! Like SPDX itself, it ignores case: commonly-used license and exception identifiers are given
+//! their canonical spelling, and any others are compared case-insensitively.
+
+use std::cmp::Ordering;
+
+use itertools::Itertools;
+use spdx_rs::models::SpdxExpression;
+
+/// The canonical spelling of commonly-used SPDX license and exception identifiers.
+const KNOWN_IDENTIFIERS: &[&str] = &[
+    "0BSD",
+    "AGPL-3.0-only",
+    "AGPL-3.0-or-later",
+    "Apache-1.1",
+    "Apache-2.0",
+    "Artistic-2.0",
+    "Autoconf-exception-3.0",
+    "BSD-1-Clause",
+    "BSD-2-Clause",
+    "BSD-3-Clause",
+    "BSD-4-Clause",
+    "BSL-1.0",
+    "Bison-exception-2.2",
+    "BlueOak-1.0.0",
+    "CC-BY-3.0",
+    "CC-BY-4.0",
+    "CC-BY-SA-3.0",
+    "CC-BY-SA-4.0",
+    "CC0-1.0",
+    "Classpath-exception-2.0",
+    "EPL-1.0",
+    "EPL-2.0",
+    "FSFAP",
+    "FSFUL",
+    "FSFULLR",
+    "GCC-exception-3.1",
+    "GFDL-1.3-only",
+    "GFDL-1.3-or-later",
+    "GPL-1.0-or-later",
+    "GPL-2.0",
+    "GPL-2.0-only",
+    "GPL-2.0-or-later",
+    "GPL-3.0",
+    "GPL-3.0-only",
+    "GPL-3.0-or-later",
+    "ISC",
+    "LGPL-2.0-only",
+    "LGPL-2.0-or-later",
+    "LGPL-2.1",
+    "LGPL-2.1-only",
+    "LGPL-2.1-or-later",
+    "LGPL-3.0",
+    "LGPL-3.0-only",
+    "LGPL-3.0-or-later",
+    "LLVM-exception",
+    "Libtool-exception",
+    "MIT",
+    "MIT-0",
+    "MPL-1.1",
+    "MPL-2.0",
+    "NCSA",
+    "OFL-1.1",
+    "OpenSSL",
+    "PSF-2.0",
+    "Python-2.0",
+    "Unicode-3.0",
+    "Unicode-DFS-2016",
+    "Unlicense",
+    "W3C",
+    "WTFPL",
+    "X11",
+    "Zlib",
+    "curl",
+    "libpng-2.0",
+    "zlib-acknowledgement",
+];
+
+/// The canonical spelling of a license or exception identifier, if it is well-known or a reference.
+fn canonical_identifier(identifier: &str) -> String {
+    let (base, plus) = match identifier.strip_suffix('+') {
+        Some(base) => (base, "+"),
+        None => (identifier, ""),
+    };
+    if let Some(known) = KNOWN_IDENTIFIERS
+        .iter()
+        .find(|known| known.eq_ignore_ascii_case(base))
+    {
+        return format!("{known}{plus}");
+    }
+    for prefix in ["LicenseRef-", "DocumentRef-", "AdditionRef-"] {
+        if base.len() > prefix.len() && base[..prefix.len()].eq_ignore_ascii_case(prefix) {
+            return format!("{prefix}{}{plus}", &base[prefix.len()..]);
+        }
+    }
+    identifier.to_string()
+}
+
+/// A simple syntax tree for a license expression.
+///
+/// Identifiers are compared case-insensitively.
+#[derive(Debug, Clone)]
+pub(crate) enum LicenseTree {
+    /// A single license identifier, possibly with an exception: `GPL-2.0-only WITH Classpath-exception-2.0`
+    License(String),
+    /// All operands apply
+    And(Vec<LicenseTree>),
+    /// Any one operand may be chosen
+    Or(Vec<LicenseTree>),
+}
+
+fn tokenize(expr: &str) -> Vec<&str> {
+    let mut tokens = vec![];
+    for word in expr.split_whitespace() {
+        let mut rest = word;
+        while !rest.is_empty() {
+            match rest.find(['(', ')']) {
+                Some(0) => {
+                    tokens.push(&rest[..1]);
+                    rest = &rest[1..];
+                }
+                Some(pos) => {
+                    tokens.push(&rest[..pos]);
+                    rest = &rest[pos..];
+                }
+                None => {
+                    tokens.push(rest);
+                    rest = "";
+                }
+            }
+        }
+    }
+    tokens
+}
+
+fn is_keyword(token: &str) -> bool {
+    ["AND", "OR", "WITH"]
+        .iter()
+        .any(|keyword| token.eq_ignore_ascii_case(keyword))
+}
+
+/// Recursive-descent parser over tokens, with the usual precedence: WITH, then AND, then OR.
+struct Parser<'a> {
+    tokens: Vec<&'a str>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.pos).copied()
+    }
+
+    fn next_if_keyword(&mut self, keyword: &str) -> bool {
+        if self
+            .peek()
+            .is_some_and(|token| token.eq_ignore_ascii_case(keyword))
+        {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn identifier(&mut self) -> Option<&'a str> {
+        let token = self
+            .peek()
+            .filter(|&t| t != "(" && t != ")" && !is_keyword(t))?;
+        self.pos += 1;
+        Some(token)
+    }
+
+    fn or_expr(&mut self) -> Option<LicenseTree> {
+        let mut operands = vec![self.and_expr()?];
+        while self.next_if_keyword("OR") {
+            operands.push(self.and_expr()?);
+        }
+        Some(if operands.len() == 1 {
+            operands.pop().expect("know there is one")
+        } else {
+            LicenseTree::Or(operands)
+        })
+    }
+
+    fn and_expr(&mut self) -> Option<LicenseTree> {
+        let mut operands = vec![self.with_expr()?];
+        while self.next_if_keyword("AND") {
+            operands.push(self.with_expr()?);
+        }
+        Some(if operands.len() == 1 {
+            operands.pop().expect("know there is one")
+        } else {
+            LicenseTree::And(operands)
+        })
+    }
+
+    fn with_expr(&mut self) -> Option<LicenseTree> {
+        if self.peek() == Some("(") {
+            self.pos += 1;
+            let inner = self.or_expr()?;
+            if self.peek() != Some(")") {
+                return None;
+            }
+            self.pos += 1;
+            return Some(inner);
+        }
+        let license = self.identifier()?;
+        if self.next_if_keyword("WITH") {
+            let exception = self.identifier()?;
+            return Some(LicenseTree::License(format!(
+                "{} WITH {}",
+                canonical_identifier(license),
+                canonical_identifier(exception)
+            )));
+        }
+        Some(LicenseTree::License(canonical_identifier(license)))
+    }
+}
+
+impl LicenseTree {
+    /// Order of the kinds of tree, for sorting
+    fn rank(&self) -> u8 {
+        match self {
+            LicenseTree::License(_) => 0,
+            LicenseTree::And(_) => 1,
+            LicenseTree::Or(_) => 2,
+        }
+    }
+
+    /// Parse the string form of a license expression.
+    pub(crate) fn parse(expr: &str) -> Option<Self> {
+        let mut parser = Parser {
+            tokens: tokenize(expr),
+            pos: 0,
+        };
+        let tree = parser.or_expr()?;
+        if parser.pos != parser.tokens.len() {
+            return None;
+        }
+        Some(tree)
+    }
+
+    /// Get the operands if this is an AND, or this as the only operand otherwise
+    fn and_operands(&self) -> &[LicenseTree] {
+        match self {
+            LicenseTree::And(operands) => operands,
+            _ => std::slice::from_ref(self),
+        }
+    }
+
+    /// Get the operands if this is an OR, or this as the only operand otherwise
+    fn or_operands(&self) -> &[LicenseTree] {
+        match self {
+            LicenseTree::Or(operands) => operands,
+            _ => std::slice::from_ref(self),
+        }
+    }
+
+    /// Get the canonical form of this tree.
+    pub(crate) fn canonicalize(self) -> Self {
+        match self {
+            LicenseTree::License(_) => self,
+            LicenseTree::And(operands) => {
+                let operands = operands
+                    .into_iter()
+                    .map(LicenseTree::canonicalize)
+                    .flat_map(|op| op.and_operands().to_vec())
+                    .sorted()
+                    .dedup()
+                    .collect_vec();
+                // A AND (A OR B) == A: drop any OR that is implied by a different operand.
+                let absorbed = operands
+                    .iter()
+                    .filter(|candidate| {
+                        !operands.iter().any(|other| {
+                            other != *candidate
+                                && other
+                                    .or_operands()
+                                    .iter()
+                                    .all(|o| candidate.or_operands().contains(o))
+                        })
+                    })
+                    .cloned()
+                    .collect_vec();
+                Self::from_operands(absorbed, LicenseTree::And)
+            }
+            LicenseTree::Or(operands) => {
+                let operands = operands
+                    .into_iter()
+                    .map(LicenseTree::canonicalize)
+                    .flat_map(|op| op.or_operands().to_vec())
+                    .sorted()
+                    .dedup()
+                    .collect_vec();
+                // A OR (A AND B) == A: drop any AND that is more restrictive than a different operand.
+                let absorbed = operands
+                    .iter()
+                    .filter(|candidate| {
+                        !operands.iter().any(|other| {
+                            other != *candidate
+                                && other
+                                    .and_operands()
+                                    .iter()
+                                    .all(|o| candidate.and_operands().contains(o))
+                        })
+                    })
+                    .cloned()
+                    .collect_vec();
+                Self::from_operands(absorbed, LicenseTree::Or)
+            }
+        }
+    }
+
+    fn from_operands(
+        mut operands: Vec<LicenseTree>,
+        make: impl FnOnce(Vec<LicenseTree>) -> LicenseTree,
+    ) -> Self {
+        if operands.len() == 1 {
+            operands.pop().expect("know there is one")
+        } else {
+            make(operands)
+        }
+    }
+}
+
+impl Ord for LicenseTree {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (LicenseTree::License(a), LicenseTree::License(b)) => a
+                .bytes()
+                .map(|c| c.to_ascii_lowercase())
+                .cmp(b.bytes().map(|c| c.to_ascii_lowercase())),
+            (LicenseTree::And(a), LicenseTree::And(b))
+            | (LicenseTree::Or(a), LicenseTree::Or(b)) => a.cmp(b),
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+}
+
+impl PartialOrd for LicenseTree {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for LicenseTree {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for LicenseTree {}
+
+impl std::fmt::Display for LicenseTree {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (operands, operator) = match self {
+            LicenseTree::License(license) => return f.write_str(license),
+            LicenseTree::And(operands) => (operands, " AND "),
+            LicenseTree::Or(operands) => (operands, " OR "),
+        };
+        let joined = operands
+            .iter()
+            .map(|op| match op {
+                LicenseTree::License(_) => op.to_string(),
+                _ => format!("({op})"),
+            })
+            .join(operator);
+        f.write_str(&joined)
+    }
+}
+
+/// Get the canonical string form of a license expression string, if it can be parsed.
+pub fn canonical_string(expr: &str) -> Option<String> {
+    LicenseTree::parse(expr).map(|tree| tree.canonicalize().to_string())
+}
+
+/// Get the canonical form of a license expression.
+///
+/// If our simplified parser cannot handle the expression, it is returned unchanged.
+pub fn canonicalize(expr: &SpdxExpression) -> SpdxExpression {
+    canonical_string(&expr.to_string())
+        .and_then(|canonical| SpdxExpression::parse(&canonical).ok())
+        .unwrap_or_else(|| expr.clone())
+}
+
+/// Are these license expressions equivalent, considering their canonical forms?
+pub fn equivalent(a: &SpdxExpression, b: &SpdxExpression) -> bool {
+    a == b || canonicalize(a) == canonicalize(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::canonical_string;
+
+    #[test]
+    fn operand_order() {
+        assert_eq!(
+            canonical_string("MIT OR Apache-2.0"),
+            canonical_string("Apache-2.0 OR MIT")
+        );
+        assert_eq!(
+            canonical_string("MIT OR Apache-2.0").unwrap(),
+            "Apache-2.0 OR MIT"
+        );
+        assert_ne!(
+            canonical_string("MIT OR Apache-2.0"),
+            canonical_string("MIT AND Apache-2.0")
+        );
+    }
+
+    #[test]
+    fn flatten_and_dedup() {
+        assert_eq!(
+            canonical_string("(MIT OR BSD-3-Clause) OR (Apache-2.0 OR MIT)").unwrap(),
+            "Apache-2.0 OR BSD-3-Clause OR MIT"
+        );
+        assert_eq!(canonical_string("MIT AND MIT").unwrap(), "MIT");
+        assert_eq!(canonical_string("((MIT))").unwrap(), "MIT");
+        assert_eq!(
+            canonical_string("mit or Zlib and BSD-2-Clause").unwrap(),
+            "MIT OR (BSD-2-Clause AND Zlib)"
+        );
+    }
+
+    #[test]
+    fn case_insensitive() {
+        assert_eq!(canonical_string("mit").unwrap(), "MIT");
+        assert_eq!(
+            canonical_string("apache-2.0 or MIT"),
+            canonical_string("MIT OR Apache-2.0")
+        );
+        assert_eq!(
+            canonical_string("gpl-2.0-or-later+").unwrap(),
+            "GPL-2.0-or-later+"
+        );
+        assert_eq!(
+            canonical_string("licenseref-Foo").unwrap(),
+            "LicenseRef-Foo"
+        );
+        // Identifiers we do not know keep their spelling, but still match regardless of case
+        assert_eq!(
+            canonical_string("Foo-License AND foo-license").unwrap(),
+            "Foo-License"
+        );
+    }
+
+    #[test]
+    fn absorption() {
+        assert_eq!(
+            canonical_string("MIT AND (MIT OR Apache-2.0)").unwrap(),
+            "MIT"
+        );
+        assert_eq!(
+            canonical_string("MIT OR (Apache-2.0 AND MIT)").unwrap(),
+            "MIT"
+        );
+        assert_eq!(
+            canonical_string("(MIT OR Zlib) AND (MIT OR Zlib OR Apache-2.0)").unwrap(),
+            "MIT OR Zlib"
+        );
+    }
+
+    #[test]
+    fn exceptions() {
+        assert_eq!(
+            canonical_string("MIT OR GPL-2.0-only with Classpath-exception-2.0").unwrap(),
+            "GPL-2.0-only WITH Classpath-exception-2.0 OR MIT"
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(canonical_string("MIT OR").is_none());
+        assert!(canonical_string("(MIT").is_none());
+        assert!(canonical_string("").is_none());
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright 2025, Collabora, Ltd.
+//
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Checking license expressions against a policy, such as a list of DFSG-free licenses.
+
+use std::fmt::Display;
+
+use serde::Deserialize;
+use spdx_rs::models::SpdxExpression;
+
+use crate::license_expression::LicenseTree;
+
+/// How a license or license expression fares against a `LicensePolicy`.
+///
+/// Ordered from best to worst.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LicenseVerdict {
+    /// Listed as allowed
+    Allowed,
+    /// Listed as needing review by a human
+    NeedsReview,
+    /// Not mentioned by the policy at all
+    Unknown,
+    /// Listed as not allowed
+    Denied,
+}
+
+impl Display for LicenseVerdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LicenseVerdict::Allowed => write!(f, "allowed"),
+            LicenseVerdict::NeedsReview => write!(f, "in need of review"),
+            LicenseVerdict::Unknown => write!(f, "not covered by the policy"),
+            LicenseVerdict::Denied => write!(f, "denied"),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LicensePolicyError {
+    #[error("Could not read license policy: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse license policy: {0}")]
+    Parse(#[from] toml::de::Error),
+}
+
+/// Lists of license identifiers, corresponding to a license policy TOML file.
+///
+/// Identifiers are compared case-insensitively. A license with an exception
+/// (`GPL-2.0-only WITH Classpath-exception-2.0`) may be listed as a whole,
+/// otherwise it is judged by the license identifier alone.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LicensePolicy {
+    /// Licenses that are acceptable, e.g. DFSG-free
+    #[serde(default)]
+    pub allow: Vec<String>,
+    /// Licenses that are not acceptable, e.g. non-free
+    #[serde(default)]
+    pub deny: Vec<String>,
+    /// Licenses that need a human to look at them
+    #[serde(default)]
+    pub review: Vec<String>,
+}
+
+fn list_contains(list: &[String], license: &str) -> bool {
+    list.iter().any(|l| l.trim().eq_ignore_ascii_case(license))
+}
+
+impl LicensePolicy {
+    /// Load a policy from a TOML file.
+    pub fn load(filename: &str) -> Result<Self, LicensePolicyError> {
+        let file = std::fs::read_to_string(filename)?;
+        Ok(toml::from_str(&file)?)
+    }
+
+    fn lookup(&self, license: &str) -> Option<LicenseVerdict> {
+        if list_contains(&self.deny, license) {
+            Some(LicenseVerdict::Denied)
+        } else if list_contains(&self.review, license) {
+            Some(LicenseVerdict::NeedsReview)
+        } else if list_contains(&self.allow, license) {
+            Some(LicenseVerdict::Allowed)
+        } else {
+            None
+        }
+    }
+
+    /// Judge a single license, possibly with an exception.
+    pub fn judge_license(&self, license: &str) -> LicenseVerdict {
+        self.lookup(license)
+            .or_else(|| {
+                license
+                    .split_once(" WITH ")
+                    .and_then(|(license, _exception)| self.lookup(license))
+            })
+            .unwrap_or(LicenseVerdict::Unknown)
+    }
+
+    fn judge_tree(&self, tree: &LicenseTree) -> LicenseVerdict {
+        match tree {
+            LicenseTree::License(license) => self.judge_license(license),
+            // Everything must be acceptable
+            LicenseTree::And(operands) => operands
+                .iter()
+                .map(|op| self.judge_tree(op))
+                .max()
+                .unwrap_or(LicenseVerdict::Unknown),
+            // We get to pick the best choice
+            LicenseTree::Or(operands) => operands
+                .iter()
+                .map(|op| self.judge_tree(op))
+                .min()
+                .unwrap_or(LicenseVerdict::Unknown),
+        }
+    }
+
+    /// Judge a license expression: for AND, every operand must be acceptable,
+    /// while for OR, the best operand is used.
+    pub fn judge(&self, expr: &SpdxExpression) -> LicenseVerdict {
+        LicenseTree::parse(&expr.to_string())
+            .map(|tree| self.judge_tree(&tree))
+            .unwrap_or(LicenseVerdict::Unknown)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LicensePolicy, LicenseTree, LicenseVerdict};
+
+    fn judge(policy: &LicensePolicy, expr: &str) -> LicenseVerdict {
+        policy.judge_tree(&LicenseTree::parse(expr).unwrap())
+    }
+
+    #[test]
+    fn verdicts() {
+        let policy: LicensePolicy = toml::from_str(
+            r#"
+            allow = ["MIT", "Apache-2.0", "GPL-2.0-only"]
+            deny = ["CC-BY-NC-4.0"]
+            review = ["LicenseRef-Proprietary-Firmware"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(judge(&policy, "mit"), LicenseVerdict::Allowed);
+        assert_eq!(judge(&policy, "CC-BY-NC-4.0"), LicenseVerdict::Denied);
+        assert_eq!(judge(&policy, "Zlib"), LicenseVerdict::Unknown);
+        assert_eq!(
+            judge(&policy, "MIT OR CC-BY-NC-4.0"),
+            LicenseVerdict::Allowed
+        );
+        assert_eq!(
+            judge(&policy, "MIT AND CC-BY-NC-4.0"),
+            LicenseVerdict::Denied
+        );
+        assert_eq!(
+            judge(&policy, "MIT AND LicenseRef-Proprietary-Firmware"),
+            LicenseVerdict::NeedsReview
+        );
+        assert_eq!(
+            judge(&policy, "GPL-2.0-only WITH Classpath-exception-2.0"),
+            LicenseVerdict::Allowed
+        );
+    }
+}
@@ -0,0 +1,199 @@
+// Copyright 2025, Collabora, Ltd.
+//
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Choosing which license field(s) of an SPDX file entry describe that file.
+
+use std::fmt::Display;
+
+use itertools::Itertools;
+use spdx_rs::models::{FileInformation, SpdxExpression};
+
+/// Which license field(s) of an SPDX file entry to take the license from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, clap::ValueEnum)]
+pub enum LicenseSelection {
+    /// Use only the concluded license
+    Concluded,
+    /// Use only the license information found in the file
+    #[default]
+    InFile,
+    /// Use the concluded license if one was asserted, otherwise the license information in the file
+    ConcludedIfAsserted,
+    /// Combine the concluded license and the license information in the file with AND
+    Both,
+}
+
+/// How to combine multiple license expressions found in a single file.
+///
+/// With REUSE, several `SPDX-License-Identifier` lines in one file usually mean that the file
+/// contains code under each of those licenses, so AND is the safe choice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, clap::ValueEnum)]
+pub enum InFileCombination {
+    /// All of the licenses apply
+    #[default]
+    And,
+    /// Any one of the licenses may be chosen
+    Or,
+}
+
+impl InFileCombination {
+    fn operator(self) -> &'static str {
+        match self {
+            InFileCombination::And => "AND",
+            InFileCombination::Or => "OR",
+        }
+    }
+}
+
+/// What to do with a file whose selected license is "NOASSERTION" or "NONE".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, clap::ValueEnum)]
+pub enum MissingLicenseAction {
+    /// Keep the file, with its license as-is
+    #[default]
+    Keep,
+    /// Leave the file out entirely
+    Omit,
+    /// Use the license shared by the other files in the nearest directory that has any
+    Inherit,
+    /// Use a default license expression supplied by the user
+    Default,
+    /// Report the files and fail
+    Fail,
+}
+
+/// Is this license expression "NOASSERTION" or "NONE", and thus does not actually name a license?
+pub fn is_missing_license(expr: &SpdxExpression) -> bool {
+    let expr = expr.to_string();
+    let expr = expr.trim();
+    expr == "NOASSERTION" || expr == "NONE"
+}
+
+/// The license field(s) a selected license actually came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LicenseSource {
+    Concluded,
+    InFile,
+    Both,
+}
+
+impl Display for LicenseSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LicenseSource::Concluded => write!(f, "concluded license"),
+            LicenseSource::InFile => write!(f, "license information in file"),
+            LicenseSource::Both => {
+                write!(f, "concluded license and license information in file")
+            }
+        }
+    }
+}
+
+/// A license expression chosen for a file, along with where it came from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SelectedLicense {
+    pub expression: SpdxExpression,
+    pub source: LicenseSource,
+}
+
+/// Get the concluded license, unless it is missing or "NOASSERTION"
+fn asserted_concluded_license(item: &FileInformation) -> Option<&SpdxExpression> {
+    item.concluded_license
+        .as_ref()
+        .filter(|concluded| **concluded != SpdxExpression::default())
+}
+
+/// Join expressions with the given operator, parenthesizing compound expressions first.
+fn join_expressions<'a>(
+    exprs: impl IntoIterator<Item = &'a SpdxExpression>,
+    operator: &str,
+) -> Option<SpdxExpression> {
+    let exprs = exprs.into_iter().unique().collect_vec();
+    if exprs.len() < 2 {
+        return exprs.first().map(|&expr| expr.clone());
+    }
+    let joined = exprs
+        .iter()
+        .map(|expr| {
+            if expr.licenses().len() == 1 {
+                expr.to_string()
+            } else {
+                format!("({})", expr)
+            }
+        })
+        .join(&format!(" {operator} "));
+    match SpdxExpression::parse(&joined) {
+        Ok(expr) => Some(expr),
+        Err(e) => {
+            // TODO this is a fallback in case of error
+            log::warn!("Could not parse combined license expression {joined}: {e}");
+            exprs.first().map(|&expr| expr.clone())
+        }
+    }
+}
+
+/// Turn the license information found in a file into a single expression.
+///
+/// Multiple entries are combined with the given operator. No entries at all results in "NOASSERTION".
+pub fn combine_license_info_in_file(
+    license_info_in_file: &[SpdxExpression],
+    combination: InFileCombination,
+) -> SpdxExpression {
+    join_expressions(license_info_in_file, combination.operator()).unwrap_or_default()
+}
+
+impl LicenseSelection {
+    /// Choose the license expression for a single file according to this policy,
+    /// combining multiple license expressions found in the file as specified.
+    pub fn select(self, item: &FileInformation, combination: InFileCombination) -> SelectedLicense {
+        let in_file = || {
+            let info = &item.license_information_in_file;
+            let distinct = info.iter().unique().count();
+            if distinct > 1 {
+                log::warn!(
+                    "{}: combining {} license expressions found in the file with {}: {}",
+                    item.file_name,
+                    distinct,
+                    combination.operator(),
+                    info.iter().unique().join(", ")
+                );
+            }
+            SelectedLicense {
+                expression: combine_license_info_in_file(info, combination),
+                source: LicenseSource::InFile,
+            }
+        };
+        match self {
+            LicenseSelection::Concluded => SelectedLicense {
+                expression: item.concluded_license.clone().unwrap_or_default(),
+                source: LicenseSource::Concluded,
+            },
+            LicenseSelection::InFile => in_file(),
+            LicenseSelection::ConcludedIfAsserted => asserted_concluded_license(item)
+                .map(|concluded| SelectedLicense {
+                    expression: concluded.clone(),
+                    source: LicenseSource::Concluded,
+                })
+                .unwrap_or_else(in_file),
+            LicenseSelection::Both => {
+                let concluded = asserted_concluded_license(item);
+                if item.license_information_in_file.is_empty() {
+                    return SelectedLicense {
+                        expression: concluded.cloned().unwrap_or_default(),
+                        source: LicenseSource::Concluded,
+                    };
+                }
+                match concluded {
+                    Some(concluded) => {
+                        let in_file = in_file().expression;
+                        SelectedLicense {
+                            expression: join_expressions([concluded, &in_file], "AND")
+                                .unwrap_or_default(),
+                            source: LicenseSource::Both,
+                        }
+                    }
+                    None => in_file(),
+                }
+            }
+        }
+    }
+}
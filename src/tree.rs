@@ -4,18 +4,26 @@
 
 use std::{
     collections::{HashMap, HashSet},
+    fmt::Display,
     hash::Hash,
-    iter::{self, FromIterator},
+    iter::FromIterator,
 };
 
 use crate::{
     cleanup::{cleanup_copyright_text, StrExt},
     deb822::dep5::FilesParagraph,
+    license_expression::canonicalize,
+    license_policy::{LicensePolicy, LicenseVerdict},
+    license_selection::{
+        is_missing_license, InFileCombination, LicenseSelection, LicenseSource,
+        MissingLicenseAction, SelectedLicense,
+    },
 };
 use atom_table::AtomTable;
 use copyright_statements::{
-    Copyright, CopyrightDecompositionError, DecomposedCopyright, YearRangeCollection,
-    YearRangeNormalizationOptions, YearSpec,
+    AliasResolution, CoalescingPolicy, Copyright, CopyrightDecompositionError, CopyrightFormatter,
+    DecomposedCopyright, Holder, HolderAliases, ImplausibleYearError, TracedYearSpec,
+    YearPlausibility, YearRangeCollection, YearRangeNormalization, YearRangeNormalizationOptions,
 };
 use derive_more::{From, Into};
 use indextree::{Arena, Node, NodeEdge, NodeId, Traverse};
@@ -30,17 +38,133 @@ struct MetadataId(usize);
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Metadata {
     pub copyright_text: String,
-    pub license: Vec<SpdxExpression>,
+    pub license: SpdxExpression,
+    /// Where the license came from, if we are recording that.
+    pub license_source: Option<LicenseSource>,
+}
+
+/// Options controlling how SPDX file information is turned into a `CopyrightDataTree`.
+#[derive(Debug, Clone, Default)]
+pub struct TreeOptions {
+    /// Which license field(s) of each file to use
+    pub license_selection: LicenseSelection,
+    /// How to combine multiple license expressions found in one file
+    pub in_file_combination: InFileCombination,
+    /// Mention the license field(s) used in the comment of each paragraph
+    pub comment_license_source: bool,
+    /// What to do with files whose license is "NOASSERTION" or "NONE"
+    pub missing_license: MissingLicenseAction,
+    /// The license to use for such files with `MissingLicenseAction::Default`
+    pub default_license: Option<SpdxExpression>,
+    /// Canonical forms of copyright holders. Copyright statements naming an alias are rewritten
+    /// with them, so that variant spellings are grouped together.
+    pub holder_aliases: HolderAliases,
+    /// How to interpret years when decomposing copyright statements to apply `holder_aliases`
+    pub year_normalization: YearRangeNormalization,
+    /// How far apart the years of a holder may be and still be merged, when grouping equivalent
+    /// statements and summarizing subtrees
+    pub year_coalescing: CoalescingPolicy,
+    /// Which years are plausible in copyright statements
+    pub year_plausibility: YearPlausibility,
+    /// What to do with files whose copyright statements have implausible years
+    pub implausible_years: ImplausibleYearAction,
+}
+
+/// What to do with a file whose copyright statement has years outside the `YearPlausibility` bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, clap::ValueEnum)]
+pub enum ImplausibleYearAction {
+    /// Do not check the years
+    Ignore,
+    /// Report the files as warnings
+    #[default]
+    Warn,
+    /// Report the files and fail
+    Fail,
 }
 
 trait MetadataStore {
     type CopyrightType;
 
-    fn get_license_for_id(&self, id: MetadataId) -> Option<&Vec<SpdxExpression>>;
+    fn get_license_for_id(&self, id: MetadataId) -> Option<&SpdxExpression>;
 
     fn get_copyright_text_for_id(&self, id: MetadataId) -> Option<&Self::CopyrightType>;
 }
 
+/// A file whose license is not allowed by a `LicensePolicy`.
+#[derive(Debug, Clone)]
+pub struct PolicyViolation {
+    pub file: String,
+    pub license: SpdxExpression,
+    pub verdict: LicenseVerdict,
+}
+
+/// What became of a file whose license was "NOASSERTION" or "NONE".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MissingLicenseOutcome {
+    /// Left in the tree with the license as-is
+    Kept,
+    /// Left out of the tree
+    Omitted,
+    /// Given a different (default or inherited) license
+    Replaced(SpdxExpression),
+}
+
+impl Display for MissingLicenseOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MissingLicenseOutcome::Kept => write!(f, "kept as-is"),
+            MissingLicenseOutcome::Omitted => write!(f, "omitted"),
+            MissingLicenseOutcome::Replaced(license) => write!(f, "using {license} instead"),
+        }
+    }
+}
+
+/// A file whose selected license was "NOASSERTION" or "NONE".
+#[derive(Debug, Clone)]
+pub struct MissingLicenseFile {
+    pub file: String,
+    pub license: SpdxExpression,
+    pub outcome: MissingLicenseOutcome,
+}
+
+/// Files had a license of "NOASSERTION" or "NONE" and `MissingLicenseAction::Fail` was chosen.
+#[derive(Debug, thiserror::Error)]
+#[error("{} files have a license of NOASSERTION or NONE", files.len())]
+pub struct MissingLicenseError {
+    pub files: Vec<MissingLicenseFile>,
+}
+
+/// A copyright statement with years that were worked out using heuristics,
+/// and the files it was found in.
+#[derive(Debug, Clone)]
+pub struct HeuristicYears {
+    pub statement: String,
+    /// The years that relied on a heuristic, see `YearProvenance::is_heuristic`
+    pub years: Vec<TracedYearSpec>,
+    pub files: Vec<String>,
+}
+
+/// A file whose copyright statement has implausible years, which may be typos.
+#[derive(Debug, Clone)]
+pub struct ImplausibleYearFile {
+    pub file: String,
+    pub errors: Vec<ImplausibleYearError>,
+}
+
+/// A file whose copyright statement has lines that could not be decomposed, which may be typos.
+#[derive(Debug, Clone)]
+pub struct CopyrightDiagnosticsFile {
+    pub file: String,
+    pub errors: Vec<CopyrightDecompositionError>,
+}
+
+/// Files had implausible years and `ImplausibleYearAction::Fail` was chosen.
+#[derive(Debug, thiserror::Error)]
+#[error("{} files have implausible years in their copyright statements", files.len())]
+pub struct ImplausibleYearsError {
+    pub files: Vec<ImplausibleYearFile>,
+}
+
 /// A part of a path, which might have a Metadata (copyright + license) associated with it, by ID.
 #[derive(Debug)]
 struct Element {
@@ -109,12 +233,32 @@ pub struct CopyrightDataTree<T = Metadata> {
     tree_arena: Arena<Element>,
     root: NodeId,
     metadata: AtomTable<T, MetadataId>,
+    options: TreeOptions,
+    missing_licenses: Vec<MissingLicenseFile>,
+    /// Nodes (and their index in `missing_licenses`) that should inherit a license from a parent directory
+    awaiting_inheritance: Vec<(usize, NodeId)>,
+    /// How many files each holder alias was applied to
+    alias_resolutions: HashMap<AliasResolution, usize>,
+    /// Every copyright statement seen, with any years that relied on a heuristic
+    heuristic_years: HashMap<String, HeuristicYears>,
+    /// Every copyright statement checked, with any implausible years
+    year_checks: HashMap<String, Vec<ImplausibleYearError>>,
+    implausible_years: Vec<ImplausibleYearFile>,
+    /// The first text seen of each normalized copyright statement
+    normalized_copyright_texts: HashMap<String, String>,
+    /// Every copyright statement diagnosed, with why any of its lines could not be decomposed
+    diagnosed_statements: HashMap<String, Vec<CopyrightDecompositionError>>,
+    copyright_diagnostics: Vec<CopyrightDiagnosticsFile>,
 }
 
 impl Extend<models::FileInformation> for CopyrightDataTree {
     fn extend<T: IntoIterator<Item = models::FileInformation>>(&mut self, iter: T) {
         for item in iter {
-            self.accumulate(&item)
+            let selected = self
+                .options
+                .license_selection
+                .select(&item, self.options.in_file_combination);
+            self.accumulate(&item, selected)
         }
     }
 }
@@ -178,6 +322,30 @@ impl<T> CopyrightDataTree<T> {
         self.tree_arena.get(id).and_then(|node| node.get().metadata)
     }
 
+    /// Find the node corresponding to the provided path, if there is one
+    fn find_node(&self, path: &str) -> Option<NodeId> {
+        path.trim_start_matches("./")
+            .split('/')
+            .try_fold(self.root, |parent_id, path_segment| {
+                parent_id
+                    .children(&self.tree_arena)
+                    .find(|&id| self.tree_arena[id].get().path_segment == path_segment)
+            })
+    }
+
+    /// Iterate over the IDs of all nodes corresponding to files (rather than directories)
+    fn file_ids(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.root
+            .descendants(&self.tree_arena)
+            .filter(move |&id| id != self.root && !self.is_directory(id))
+    }
+
+    /// Get the path of a node, relative to the root, without a leading "./"
+    fn get_relative_path(&self, id: NodeId) -> Option<String> {
+        self.get_path(id)
+            .map(|path| path.trim_start_matches("./").to_string())
+    }
+
     /// Propagate metadata IDs upward when all children have the same metadata ID
     pub fn propagate_metadata(&mut self) {
         // Record the visit order so we can be done with the iterator and modify the tree
@@ -196,13 +364,24 @@ impl<T> CopyrightDataTree<T> {
 }
 
 impl<T: Clone + Hash + Eq> CopyrightDataTree<T> {
-    fn new() -> Self {
+    /// Create an empty tree, which will use the provided options when accumulating file information.
+    pub fn with_options(options: TreeOptions) -> Self {
         let mut arena = Arena::new();
         let root = arena.new_node(Element::new("."));
         Self {
             tree_arena: arena,
             root,
             metadata: Default::default(),
+            options,
+            missing_licenses: vec![],
+            awaiting_inheritance: vec![],
+            alias_resolutions: HashMap::new(),
+            heuristic_years: HashMap::new(),
+            year_checks: HashMap::new(),
+            implausible_years: vec![],
+            normalized_copyright_texts: HashMap::new(),
+            diagnosed_statements: HashMap::new(),
+            copyright_diagnostics: vec![],
         }
     }
 
@@ -214,25 +393,387 @@ impl<T: Clone + Hash + Eq> CopyrightDataTree<T> {
 }
 
 impl CopyrightDataTree<Metadata> {
+    /// Add a single element of SPDX FileInformation to the tree, with a license already selected
+    /// for it, such as when the selection was needed for something else first.
+    ///
+    /// Otherwise, `extend` selects the license according to the `TreeOptions`.
+    pub fn add_file(&mut self, item: &models::FileInformation, selected: SelectedLicense) {
+        self.accumulate(item, selected)
+    }
+
     /// Add a single element of SPDX FileInformation to the tree, after cleanup and processing.
-    fn accumulate(&mut self, item: &models::FileInformation) {
-        let license = item.license_information_in_file.clone();
+    fn accumulate(&mut self, item: &models::FileInformation, selected: SelectedLicense) {
+        let license_source = if self.options.comment_license_source {
+            Some(selected.source)
+        } else {
+            None
+        };
+        let filename = item.file_name.trim_start_matches("./");
+        // Canonicalize so that equivalent expressions get grouped together
+        let mut license = canonicalize(&selected.expression);
+        let missing = is_missing_license(&license);
+        if missing {
+            let outcome = self.missing_license_outcome();
+            self.missing_licenses.push(MissingLicenseFile {
+                file: filename.to_string(),
+                license: license.clone(),
+                outcome: outcome.clone(),
+            });
+            match outcome {
+                MissingLicenseOutcome::Kept => {}
+                MissingLicenseOutcome::Omitted => return,
+                MissingLicenseOutcome::Replaced(replacement) => license = replacement,
+            }
+        }
         let copyright_text = cleanup_copyright_text(&item.copyright_text).join("\n");
+        // Before any rewriting, which would make every year four digits
+        self.record_heuristic_years(filename, &copyright_text);
+        self.check_year_plausibility(filename, &copyright_text);
+        self.diagnose_copyright(filename, &copyright_text);
+        let copyright_text = self.resolve_holder_aliases(copyright_text);
+        let copyright_text = self.intern_copyright_text(copyright_text);
         let metadata_id = self.find_or_insert_metadata(Metadata {
             copyright_text,
             license,
+            license_source,
         });
-        let filename = item.file_name.trim_start_matches("./");
         let id = find_or_create_node(&mut self.tree_arena, self.root, filename);
         let node = self.tree_arena.get_mut(id).unwrap();
         node.get_mut().metadata = Some(metadata_id);
+        if missing && self.options.missing_license == MissingLicenseAction::Inherit {
+            self.awaiting_inheritance
+                .push((self.missing_licenses.len() - 1, id));
+        }
+    }
+
+    /// Record the file if the years of its copyright statement relied on a heuristic.
+    fn record_heuristic_years(&mut self, filename: &str, copyright_text: &str) {
+        let options = self.options.year_normalization;
+        let statement = self
+            .heuristic_years
+            .entry(copyright_text.to_string())
+            .or_insert_with(|| HeuristicYears {
+                statement: copyright_text.to_string(),
+                years: Copyright::try_parse_with_provenance(options, copyright_text)
+                    .map(|(_, years)| {
+                        years
+                            .into_iter()
+                            .filter(|year| year.provenance.is_heuristic())
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                files: vec![],
+            });
+        if !statement.years.is_empty() {
+            statement.files.push(filename.to_string());
+        }
+    }
+
+    /// Record the file if its copyright statement has implausible years.
+    fn check_year_plausibility(&mut self, filename: &str, copyright_text: &str) {
+        if self.options.implausible_years == ImplausibleYearAction::Ignore {
+            return;
+        }
+        let options = self.options.year_normalization;
+        let plausibility = self.options.year_plausibility;
+        let errors = self
+            .year_checks
+            .entry(copyright_text.to_string())
+            .or_insert_with(|| plausibility.check_statement(options, copyright_text));
+        if !errors.is_empty() {
+            self.implausible_years.push(ImplausibleYearFile {
+                file: filename.to_string(),
+                errors: errors.clone(),
+            });
+        }
+    }
+
+    /// Record the file if some lines of its copyright statement could not be decomposed.
+    fn diagnose_copyright(&mut self, filename: &str, copyright_text: &str) {
+        let options = self.options.year_normalization;
+        let errors = self
+            .diagnosed_statements
+            .entry(copyright_text.to_string())
+            .or_insert_with(|| Copyright::diagnose(options, copyright_text));
+        if !errors.is_empty() {
+            self.copyright_diagnostics.push(CopyrightDiagnosticsFile {
+                file: filename.to_string(),
+                errors: errors.clone(),
+            });
+        }
+    }
+
+    /// The files whose copyright statements have lines that could not be decomposed, and why.
+    pub fn copyright_diagnostics(&self) -> &[CopyrightDiagnosticsFile] {
+        &self.copyright_diagnostics
+    }
+
+    /// The files whose copyright statements have implausible years, so they can be reviewed.
+    ///
+    /// Returns an error if there are any such files and `ImplausibleYearAction::Fail` was chosen.
+    pub fn implausible_years(&self) -> Result<&[ImplausibleYearFile], ImplausibleYearsError> {
+        if self.options.implausible_years == ImplausibleYearAction::Fail
+            && !self.implausible_years.is_empty()
+        {
+            return Err(ImplausibleYearsError {
+                files: self.implausible_years.clone(),
+            });
+        }
+        Ok(&self.implausible_years)
+    }
+
+    /// The copyright statements with years that relied on a heuristic, so they can be reviewed.
+    pub fn heuristic_years(&self) -> Vec<&HeuristicYears> {
+        self.heuristic_years
+            .values()
+            .filter(|statement| !statement.years.is_empty())
+            .sorted_by(|a, b| a.statement.cmp(&b.statement))
+            .collect()
+    }
+
+    /// Rewrite a copyright statement with canonical holders, if we have any aliases
+    /// and the statement can be decomposed, recording the aliases applied.
+    fn resolve_holder_aliases(&mut self, copyright_text: String) -> String {
+        if self.options.holder_aliases.is_empty() {
+            return copyright_text;
+        }
+        let Ok((copyright, resolutions)) = Copyright::try_parse_with_aliases(
+            self.options.year_normalization,
+            &self.options.holder_aliases,
+            &copyright_text,
+        ) else {
+            return copyright_text;
+        };
+        // Leave statements no alias applies to exactly as they were
+        if resolutions.is_empty() {
+            return copyright_text;
+        }
+        for resolution in resolutions {
+            *self.alias_resolutions.entry(resolution).or_default() += 1;
+        }
+        copyright.to_string()
+    }
+
+    /// Intern a statement that can be decomposed by its normalized form (see `Copyright::normalize`),
+    /// so that equivalent statements share metadata.
+    ///
+    /// Returns the text of the first equivalent statement seen, which is what gets output.
+    fn intern_copyright_text(&mut self, copyright_text: String) -> String {
+        match Copyright::try_parse(self.options.year_normalization, &copyright_text) {
+            Ok(copyright) if !copyright.decomposed_lines().is_empty() => self
+                .normalized_copyright_texts
+                .entry(
+                    copyright
+                        .normalize_with(self.options.year_coalescing)
+                        .to_string(),
+                )
+                .or_insert(copyright_text)
+                .clone(),
+            _ => copyright_text,
+        }
+    }
+
+    /// The holder aliases that were applied, each with the number of files it was applied to.
+    pub fn alias_resolutions(&self) -> Vec<(&AliasResolution, usize)> {
+        self.alias_resolutions
+            .iter()
+            .map(|(resolution, &count)| (resolution, count))
+            .sorted()
+            .collect()
+    }
+
+    /// Every copyright holder named in a decomposable statement, with the number of files naming them.
+    pub fn holder_file_counts(&self) -> Vec<(Holder, usize)> {
+        let mut holders_per_metadata: HashMap<MetadataId, Vec<Holder>> = HashMap::new();
+        let mut counts: HashMap<Holder, usize> = HashMap::new();
+        for id in self.file_ids() {
+            let Some(metadata_id) = self.get_metadata_id(id) else {
+                continue;
+            };
+            let holders = holders_per_metadata.entry(metadata_id).or_insert_with(|| {
+                self.get_copyright_text_for_id(metadata_id)
+                    .and_then(|text| {
+                        Copyright::try_parse(self.options.year_normalization, text).ok()
+                    })
+                    .map(|copyright| {
+                        copyright
+                            .decomposed_lines()
+                            .iter()
+                            .map(|line| line.holder.clone())
+                            .unique()
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            });
+            for holder in holders.iter() {
+                *counts.entry(holder.clone()).or_default() += 1;
+            }
+        }
+        counts.into_iter().sorted().collect()
+    }
+
+    /// Decide what to do with a file with a missing license when it is first added.
+    fn missing_license_outcome(&self) -> MissingLicenseOutcome {
+        match self.options.missing_license {
+            MissingLicenseAction::Omit => MissingLicenseOutcome::Omitted,
+            MissingLicenseAction::Default => match &self.options.default_license {
+                // Like every other license, so that equivalent expressions are grouped together
+                Some(license) => MissingLicenseOutcome::Replaced(canonicalize(license)),
+                None => {
+                    log::warn!("No default license provided, keeping missing license as-is");
+                    MissingLicenseOutcome::Kept
+                }
+            },
+            // Inheriting has to wait until we have the whole tree
+            MissingLicenseAction::Keep
+            | MissingLicenseAction::Inherit
+            | MissingLicenseAction::Fail => MissingLicenseOutcome::Kept,
+        }
+    }
+
+    /// Find the license of the nearest ancestor directory of `id` that contains files with a known license,
+    /// if those files all share a single license.
+    fn inherited_license(&self, id: NodeId, pending: &HashSet<NodeId>) -> Option<SpdxExpression> {
+        for ancestor in id.ancestors(&self.tree_arena).skip(1) {
+            let mut licenses = ancestor
+                .descendants(&self.tree_arena)
+                .filter(|descendant| {
+                    !pending.contains(descendant) && !self.is_directory(*descendant)
+                })
+                .filter_map(|descendant| self.get_license_for_id(self.get_metadata_id(descendant)?))
+                .unique();
+            match (licenses.next(), licenses.next()) {
+                (Some(license), None) => return Some(license.clone()),
+                // Mixed licenses, and going further up will not help
+                (Some(_), Some(_)) => return None,
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Finish handling files whose license was "NOASSERTION" or "NONE", returning all such files.
+    ///
+    /// Call this after adding all files, and before `propagate_metadata()`.
+    /// Files that should inherit a license get it here, if possible.
+    /// Returns an error if there are any such files and `MissingLicenseAction::Fail` was chosen.
+    pub fn resolve_missing_licenses(
+        &mut self,
+    ) -> Result<&[MissingLicenseFile], MissingLicenseError> {
+        let awaiting_inheritance = std::mem::take(&mut self.awaiting_inheritance);
+        let pending: HashSet<NodeId> = awaiting_inheritance.iter().map(|&(_, id)| id).collect();
+        for (index, id) in awaiting_inheritance {
+            let inherited = self.inherited_license(id, &pending);
+            let metadata = self
+                .get_metadata_id(id)
+                .and_then(|metadata_id| self.metadata.get(metadata_id))
+                .cloned();
+            if let (Some(license), Some(metadata)) = (inherited, metadata) {
+                let metadata_id = self.find_or_insert_metadata(Metadata {
+                    license: license.clone(),
+                    ..metadata
+                });
+                self.set_metadata_id_for_node(id, metadata_id);
+                self.missing_licenses[index].outcome = MissingLicenseOutcome::Replaced(license);
+            }
+        }
+        if self.options.missing_license == MissingLicenseAction::Fail
+            && !self.missing_licenses.is_empty()
+        {
+            return Err(MissingLicenseError {
+                files: self.missing_licenses.clone(),
+            });
+        }
+        Ok(&self.missing_licenses)
+    }
+
+    /// Judge the license of every file, returning the IDs of file nodes with their license and verdict.
+    fn judge_files<'a>(
+        &'a self,
+        policy: &'a LicensePolicy,
+    ) -> impl Iterator<Item = (NodeId, &'a SpdxExpression, LicenseVerdict)> + 'a {
+        let mut verdicts: HashMap<MetadataId, LicenseVerdict> = HashMap::new();
+        self.file_ids().filter_map(move |id| {
+            let metadata_id = self.get_metadata_id(id)?;
+            let license = &self.metadata.get(metadata_id)?.license;
+            let verdict = *verdicts
+                .entry(metadata_id)
+                .or_insert_with(|| policy.judge(license));
+            Some((id, license, verdict))
+        })
+    }
+
+    /// Get the license of a file in the tree, after any missing license has been resolved.
+    ///
+    /// Returns `None` if the file is not in the tree, such as when it was omitted.
+    pub fn file_license(&self, path: &str) -> Option<&SpdxExpression> {
+        let metadata_id = self.get_metadata_id(self.find_node(path)?)?;
+        Some(&self.metadata.get(metadata_id)?.license)
+    }
+
+    /// Check the license of every file against the policy, returning those files that are not allowed.
+    pub fn check_license_policy(&self, policy: &LicensePolicy) -> Vec<PolicyViolation> {
+        self.judge_files(policy)
+            .filter(|(_, _, verdict)| *verdict != LicenseVerdict::Allowed)
+            .filter_map(|(id, license, verdict)| {
+                Some(PolicyViolation {
+                    file: self.get_relative_path(id)?,
+                    license: license.clone(),
+                    verdict,
+                })
+            })
+            .collect()
+    }
+
+    /// Compute a minimal list of `Files-Excluded` patterns that would remove every file whose license
+    /// is denied by the policy, without removing any other file.
+    ///
+    /// A directory is listed as a single pattern if everything in it is denied.
+    pub fn files_excluded_patterns(&self, policy: &LicensePolicy) -> Vec<String> {
+        let denied_files: HashSet<NodeId> = self
+            .judge_files(policy)
+            .filter(|(_, _, verdict)| *verdict == LicenseVerdict::Denied)
+            .map(|(id, _, _)| id)
+            .collect();
+
+        // Children are finished before their parents, so we can work upward.
+        let mut fully_denied: HashSet<NodeId> = HashSet::new();
+        for edge in self.root.traverse(&self.tree_arena) {
+            if let NodeEdge::End(id) = edge {
+                let denied = if self.is_directory(id) {
+                    id.children(&self.tree_arena)
+                        .all(|child_id| fully_denied.contains(&child_id))
+                } else {
+                    denied_files.contains(&id)
+                };
+                if denied {
+                    fully_denied.insert(id);
+                }
+            }
+        }
+
+        // Now take only the top-most fully-denied nodes.
+        let mut patterns = vec![];
+        let mut traversal = self.root.traverse(&self.tree_arena);
+        while let Some(edge) = traversal.next() {
+            if let NodeEdge::Start(id) = edge {
+                if fully_denied.contains(&id) {
+                    skip_until_end_of_id(&mut traversal, id);
+                    if let Some(pattern) = self.get_pattern(id) {
+                        patterns.push(process_file_pattern(&pattern));
+                    }
+                }
+            }
+        }
+        patterns.sort();
+        patterns
     }
 }
 
 impl MetadataStore for CopyrightDataTree {
     type CopyrightType = String;
 
-    fn get_license_for_id(&self, id: MetadataId) -> Option<&Vec<SpdxExpression>> {
+    fn get_license_for_id(&self, id: MetadataId) -> Option<&SpdxExpression> {
         self.metadata.get(id).map(|m| &m.license)
     }
 
@@ -243,7 +784,7 @@ impl MetadataStore for CopyrightDataTree {
 
 impl FromIterator<models::FileInformation> for CopyrightDataTree {
     fn from_iter<T: IntoIterator<Item = models::FileInformation>>(iter: T) -> Self {
-        let mut ret = Self::new();
+        let mut ret = Self::with_options(TreeOptions::default());
         ret.extend(iter);
         ret
     }
@@ -282,14 +823,14 @@ impl Iterator for NodeIdsWithMetadata<'_> {
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 struct ParsedMetadata {
-    license: Vec<SpdxExpression>,
+    license: SpdxExpression,
     copyright: Copyright,
 }
 
 impl MetadataStore for CopyrightDataTree<ParsedMetadata> {
     type CopyrightType = Copyright;
 
-    fn get_license_for_id(&self, id: MetadataId) -> Option<&Vec<SpdxExpression>> {
+    fn get_license_for_id(&self, id: MetadataId) -> Option<&SpdxExpression> {
         self.metadata.get(id).map(|m| &m.license)
     }
 
@@ -299,44 +840,80 @@ impl MetadataStore for CopyrightDataTree<ParsedMetadata> {
 }
 
 impl CopyrightDataTree {
+    /// Parse and normalize every copyright statement.
+    ///
+    /// Metadata is interned again by the normalized copyright, so statements that differ only in
+    /// line order, year order, or the like end up sharing a `MetadataId`.
     fn perform_copyright_decomposition(
         self,
         options: impl YearRangeNormalizationOptions + Copy,
     ) -> Result<CopyrightDataTree<ParsedMetadata>, CopyrightDecompositionError> {
-        let metadata = self
-            .metadata
-            .try_transform_res(
-                |metadata| -> Result<ParsedMetadata, CopyrightDecompositionError> {
-                    let copyright = Copyright::try_parse(options, &metadata.copyright_text)?;
-                    Ok(ParsedMetadata {
+        let coalescing = self.options.year_coalescing;
+        let mut parsed = CopyrightDataTree::with_options(self.options);
+        let mut new_ids: HashMap<MetadataId, MetadataId> = HashMap::new();
+        let mut tree_arena = self.tree_arena;
+        for node in tree_arena.iter_mut() {
+            let element = node.get_mut();
+            let Some(old_id) = element.metadata else {
+                continue;
+            };
+            let new_id = match new_ids.get(&old_id) {
+                Some(&new_id) => new_id,
+                None => {
+                    let metadata = self
+                        .metadata
+                        .get(old_id)
+                        .expect("IDs in the tree should be in the table");
+                    let copyright = Copyright::try_parse(options, &metadata.copyright_text)?
+                        .normalize_with(coalescing);
+                    let new_id = parsed.find_or_insert_metadata(ParsedMetadata {
                         license: metadata.license.clone(),
                         copyright,
-                    })
-                },
-            )
-            .map_err(|e| {
-                e.as_transform_function_error()
-                    .expect("Our transform should be acceptable")
-                    .clone()
-            })?;
+                    });
+                    new_ids.insert(old_id, new_id);
+                    new_id
+                }
+            };
+            element.metadata = Some(new_id);
+        }
         Ok(CopyrightDataTree {
-            tree_arena: self.tree_arena,
+            tree_arena,
             root: self.root,
-            metadata,
+            metadata: parsed.metadata,
+            options: parsed.options,
+            missing_licenses: self.missing_licenses,
+            awaiting_inheritance: self.awaiting_inheritance,
+            alias_resolutions: self.alias_resolutions,
+            heuristic_years: self.heuristic_years,
+            year_checks: self.year_checks,
+            implausible_years: self.implausible_years,
+            normalized_copyright_texts: self.normalized_copyright_texts,
+            diagnosed_statements: self.diagnosed_statements,
+            copyright_diagnostics: self.copyright_diagnostics,
         })
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct LicenseAndHolders {
-    license: Vec<SpdxExpression>,
-    holders: Vec<String>,
+    license: SpdxExpression,
+    holders: Vec<Holder>,
+    /// Lines of free text that could not be decomposed: these must match exactly to merge
+    residue: Vec<String>,
 }
 
 impl LicenseAndHolders {
-    fn new(license: Vec<SpdxExpression>, holders: impl IntoIterator<Item = String>) -> Self {
-        let holders: Vec<String> = holders.into_iter().sorted().collect();
-        Self { license, holders }
+    fn new(
+        license: SpdxExpression,
+        holders: impl IntoIterator<Item = Holder>,
+        residue: impl IntoIterator<Item = String>,
+    ) -> Self {
+        let holders: Vec<Holder> = holders.into_iter().sorted().dedup().collect();
+        Self {
+            license,
+            holders,
+            residue: residue.into_iter().collect(),
+        }
     }
 }
 #[derive(Debug, Clone)]
@@ -383,7 +960,7 @@ struct SummarizerOutput {
 
 #[derive(Debug, Clone, Default)]
 struct SubtreeSummarizer {
-    ranges_per_holder: HashMap<String, YearRangeCollection>,
+    ranges_per_holder: HashMap<Holder, YearRangeCollection>,
     license_and_holders_metadata_ids: HashMap<LicenseAndHolders, HashSet<MetadataId>>,
     metadata_id_usage_count: UsageCount<MetadataId>,
 }
@@ -409,23 +986,16 @@ impl SubtreeSummarizer {
         let copyright = metadata_source.get_copyright_text_for_id(metadata_id);
         let license = metadata_source.get_license_for_id(metadata_id);
         if let (Some(copyright), Some(license)) = (copyright, license) {
-            let license_and_holders = match copyright {
-                Copyright::Decomposable(single_line) => {
-                    self.record_ranges_for_line_holder(single_line);
-                    LicenseAndHolders::new(license.clone(), iter::once(single_line.holder.clone()))
-                }
-                Copyright::MultilineDecomposable(lines) => {
-                    for line in lines {
-                        self.record_ranges_for_line_holder(line);
-                    }
-
-                    LicenseAndHolders::new(
-                        license.clone(),
-                        lines.iter().map(|item| item.holder.clone()),
-                    )
-                }
-                Copyright::Complex(_) => panic!("hey we didn't consider this case"),
-            };
+            // The decomposed lines get merged, while any free text is carried along as-is.
+            let lines = copyright.decomposed_lines();
+            for line in lines {
+                self.record_ranges_for_line_holder(line);
+            }
+            let license_and_holders = LicenseAndHolders::new(
+                license.clone(),
+                lines.iter().map(|item| item.holder.clone()),
+                copyright.residue_lines().into_iter().map(str::to_string),
+            );
             self.license_and_holders_metadata_ids
                 .entry(license_and_holders)
                 .or_default()
@@ -433,8 +1003,9 @@ impl SubtreeSummarizer {
         }
     }
 
-    fn into_results(self) -> Vec<SummarizerOutput> {
-        let mut ranges_per_holder = self.ranges_per_holder;
+    /// Merge the recorded years of each holder as far as `coalescing` allows.
+    fn into_results(self, coalescing: CoalescingPolicy) -> Vec<SummarizerOutput> {
+        let ranges_per_holder = self.ranges_per_holder;
         let metadata_id_usage_count = &self.metadata_id_usage_count;
         let mut ret = vec![];
         for (license_and_holders, metadata_ids) in self.license_and_holders_metadata_ids.into_iter()
@@ -444,31 +1015,20 @@ impl SubtreeSummarizer {
                 .map(|id| metadata_id_usage_count.get(id))
                 .sum();
             let license = license_and_holders.license;
-            let mut copyrights = license_and_holders
+            let copyrights = license_and_holders
                 .holders
                 .into_iter()
                 .map(|holder| {
+                    // A holder may appear with more than one license, so don't remove it
                     let years = ranges_per_holder
-                        .remove(&holder)
+                        .get(&holder)
+                        .cloned()
                         .expect("Should only get here if we've seen this holder")
-                        .into_coalesced_vec()
-                        .into_iter()
-                        .map(|yr| {
-                            if yr.is_single_year() {
-                                YearSpec::SingleYear(yr.begin())
-                            } else {
-                                YearSpec::ClosedRange(yr)
-                            }
-                        })
-                        .collect_vec();
+                        .into_coalesced_specs(coalescing);
                     DecomposedCopyright { years, holder }
                 })
                 .collect_vec();
-            let copyright = if copyrights.len() == 1 {
-                Copyright::Decomposable(copyrights.pop().expect("know this will succeed"))
-            } else {
-                Copyright::MultilineDecomposable(copyrights)
-            };
+            let copyright = Copyright::from_parts(copyrights, license_and_holders.residue);
             ret.push(SummarizerOutput {
                 metadata: ParsedMetadata { license, copyright },
                 usage_count,
@@ -501,11 +1061,33 @@ pub fn summarize_metadata(
         .collect();
 }
 
+fn format_copyright(
+    copyright_text: &str,
+    formatter: Option<&CopyrightFormatter>,
+    options: impl YearRangeNormalizationOptions + Copy,
+) -> String {
+    formatter
+        .and_then(|formatter| {
+            Copyright::try_parse(options, copyright_text)
+                .ok()
+                .map(|copyright| formatter.format(&copyright))
+        })
+        .unwrap_or_else(|| copyright_text.to_string())
+}
+
 fn process_file_pattern(path: &str) -> String {
     path.trim_start_matches("./").replace(' ', "?") // apparently space is a reserved separator
 }
 
-pub fn make_paragraphs(cdt: CopyrightDataTree) -> impl Iterator<Item = FilesParagraph> {
+/// Turn the tree into paragraphs for the copyright file.
+///
+/// If a formatter is given, copyright statements that can be parsed are rewritten with it,
+/// otherwise they are output as found.
+pub fn make_paragraphs(
+    cdt: CopyrightDataTree,
+    formatter: Option<&CopyrightFormatter>,
+    options: impl YearRangeNormalizationOptions + Copy,
+) -> impl Iterator<Item = FilesParagraph> {
     let mut paras = vec![];
     let grouped = NodeIdsWithMetadata::new(&cdt).chunk_by(|&id| cdt.get_metadata_id(id));
     for (key, grouped_ids) in &grouped {
@@ -518,35 +1100,193 @@ pub fn make_paragraphs(cdt: CopyrightDataTree) -> impl Iterator<Item = FilesPara
                 .collect_vec()
                 .join("\n");
 
-            // Parenthesize complex expressions before merging
-            let initial_license_string = metadata
-                .license
-                .iter()
-                .map(|expr| {
-                    if expr.licenses().len() == 1 {
-                        expr.to_string()
-                    } else {
-                        format!("({})", expr)
-                    }
-                })
-                .join(" OR ");
-
-            // Re-parse as expression, in case this simplifies things.
-            let license_string =
-                SpdxExpression::parse(&initial_license_string).map(|expr| expr.to_string());
-
             // Use Debian names for licenses
-            let license_string = license_string
-                .unwrap_or(initial_license_string)
-                .licenses_spdx_to_debian();
+            let license_string = metadata.license.to_string().licenses_spdx_to_debian();
+
+            let comment = metadata
+                .license_source
+                .map(|source| format!("License taken from the {source}.").into());
 
             paras.push(FilesParagraph {
                 files: files.into(),
-                copyright: metadata.copyright_text.clone().into(),
+                copyright: format_copyright(&metadata.copyright_text, formatter, options).into(),
                 license: license_string.into(),
-                comment: None,
+                comment,
             })
         }
     }
     paras.into_iter()
 }
+
+#[cfg(test)]
+mod tests {
+    use spdx_rs::models::{FileInformation, SpdxExpression};
+
+    use copyright_statements::CoalescingPolicy;
+    use itertools::Itertools;
+
+    use super::{make_paragraphs, CopyrightDataTree, SubtreeSummarizer, TreeOptions};
+    use crate::{
+        holder_aliases::parse_toml_holder_aliases,
+        license_selection::{LicenseSource, SelectedLicense},
+    };
+
+    fn add_file(tree: &mut CopyrightDataTree, file_name: &str, copyright_text: &str) {
+        let item = FileInformation {
+            file_name: file_name.to_string(),
+            copyright_text: Some(copyright_text.to_string()),
+            ..Default::default()
+        };
+        tree.add_file(
+            &item,
+            SelectedLicense {
+                expression: SpdxExpression::parse("MIT").unwrap(),
+                source: LicenseSource::InFile,
+            },
+        );
+    }
+
+    #[test]
+    fn equivalent_statements_share_a_paragraph() {
+        let options = TreeOptions::default();
+        let year_normalization = options.year_normalization;
+        let mut tree = CopyrightDataTree::with_options(options);
+        add_file(
+            &mut tree,
+            "./a.c",
+            "Copyright 2019, Foo\nCopyright 2020-2022, Foo",
+        );
+        add_file(&mut tree, "./b.c", "Copyright 2019-2022, Foo");
+        // Missing a year, so not the same
+        add_file(
+            &mut tree,
+            "./c.c",
+            "Copyright 2019, Foo\nCopyright 2021-2022, Foo",
+        );
+
+        let paragraphs = make_paragraphs(tree, None, year_normalization).collect::<Vec<_>>();
+        assert_eq!(paragraphs.len(), 2);
+        assert_eq!(paragraphs[0].files.0, "a.c\nb.c");
+        // As first written
+        assert_eq!(paragraphs[0].copyright.0, "2019, Foo\n2020-2022, Foo");
+        assert_eq!(paragraphs[1].files.0, "c.c");
+        assert_eq!(paragraphs[1].copyright.0, "2019, Foo\n2021-2022, Foo");
+    }
+
+    #[test]
+    fn holder_aliases_only_rewrite_matches() {
+        let holder_aliases = parse_toml_holder_aliases(
+            r#"
+[[holder]]
+name = "Collabora, Ltd."
+aliases = ["Collabora Ltd"]
+"#,
+        )
+        .unwrap();
+        let mut tree = CopyrightDataTree::with_options(TreeOptions {
+            holder_aliases,
+            ..Default::default()
+        });
+        assert_eq!(
+            tree.resolve_holder_aliases("Copyright 2020, 2019, Jane Doe".to_string()),
+            "Copyright 2020, 2019, Jane Doe"
+        );
+        assert_eq!(
+            tree.resolve_holder_aliases("Copyright (c) 2019, Collabora Ltd".to_string()),
+            "2019, Collabora, Ltd."
+        );
+    }
+
+    #[test]
+    fn summarize_partly_decomposed() {
+        let options = TreeOptions::default();
+        let year_normalization = options.year_normalization;
+        let mut tree = CopyrightDataTree::with_options(options);
+        add_file(
+            &mut tree,
+            "./src/a.c",
+            "Copyright 2019, Foo\nBased on work by Bar",
+        );
+        add_file(
+            &mut tree,
+            "./src/b.c",
+            "Copyright 2020-2021, Foo\nBased on work by Bar",
+        );
+        add_file(&mut tree, "./src/c.c", "Copyright 2022, Baz");
+        let src = tree.find_node("src").unwrap();
+        let parsed = tree
+            .perform_copyright_decomposition(year_normalization)
+            .unwrap();
+
+        let mut summarizer = SubtreeSummarizer::default();
+        for child in src.children(&parsed.tree_arena) {
+            summarizer.accumulate(&parsed, parsed.get_metadata_id(child).unwrap());
+        }
+        let summaries = summarizer
+            .into_results(CoalescingPolicy::Strict)
+            .into_iter()
+            .map(|output| {
+                (
+                    output.metadata.copyright.to_string(),
+                    output.usage_count,
+                    output.metadata_ids.len(),
+                )
+            })
+            .sorted()
+            .collect_vec();
+        assert_eq!(
+            summaries,
+            vec![
+                ("2019-2021, Foo\nBased on work by Bar".to_string(), 2, 2),
+                ("2022, Baz".to_string(), 1, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn copyright_diagnostics() {
+        let mut tree = CopyrightDataTree::with_options(TreeOptions::default());
+        add_file(&mut tree, "./a.c", "Copyright 2019, Foo");
+        add_file(&mut tree, "./b.c", "Copyright 2022-2019, Foo");
+        let diagnostics = tree.copyright_diagnostics();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].file, "b.c");
+        assert_eq!(diagnostics[0].errors.len(), 1);
+    }
+
+    #[test]
+    fn unformatted_text_unchanged() {
+        let options = TreeOptions::default();
+        let year_normalization = options.year_normalization;
+        let mut tree = CopyrightDataTree::with_options(options);
+        add_file(
+            &mut tree,
+            "./a.c",
+            "Copyright 2021, 2019, Foo <foo@example.org>\nAll rights reserved.",
+        );
+
+        let paragraphs = make_paragraphs(tree, None, year_normalization).collect::<Vec<_>>();
+        assert_eq!(paragraphs.len(), 1);
+        // Cleaned up, but neither reordered nor merged
+        assert_eq!(
+            paragraphs[0].copyright.0,
+            "2021, 2019, Foo <foo@example.org>\nAll rights reserved."
+        );
+    }
+
+    #[test]
+    fn coalescing_policy_groups_statements() {
+        let options = TreeOptions {
+            year_coalescing: CoalescingPolicy::MaxGap(1),
+            ..Default::default()
+        };
+        let year_normalization = options.year_normalization;
+        let mut tree = CopyrightDataTree::with_options(options);
+        add_file(&mut tree, "./a.c", "Copyright 2019, 2021, Foo");
+        add_file(&mut tree, "./b.c", "Copyright 2019-2021, Foo");
+
+        let paragraphs = make_paragraphs(tree, None, year_normalization).collect::<Vec<_>>();
+        assert_eq!(paragraphs.len(), 1);
+        assert_eq!(paragraphs[0].files.0, "a.c\nb.c");
+    }
+}